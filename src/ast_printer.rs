@@ -1,5 +1,6 @@
 use crate::expr::{Expr, ExprVisitor};
 use crate::token::{Token, LiteralValue};
+use crate::stmt::Stmt;
 
 pub struct AstPrinter;
 
@@ -12,6 +13,50 @@ impl AstPrinter {
         expr.accept(self)
     }
 
+    // One line per statement, without the bodies of blocks, loops and functions: "var x = (+ 1 2)", "while (< i 3)"
+    pub fn print_stmt(&mut self, stmt: &Stmt) -> String {
+        match stmt {
+            Stmt::Expression { expression } => self.print(expression),
+            Stmt::Print { expressions } => {
+                let values: Vec<String> = expressions.iter().map(|expression| self.print(expression)).collect();
+                format!("print {}", values.join(", "))
+            }
+            Stmt::Var { name, initializer: Some(initializer) } => format!("var {} = {}", name.lexeme, self.print(initializer)),
+            Stmt::Var { name, initializer: None } => format!("var {}", name.lexeme),
+            Stmt::Block { .. } => "block".to_string(),
+            Stmt::If { condition, .. } => format!("if {}", self.print(condition)),
+            Stmt::While { condition, .. } => format!("while {}", self.print(condition)),
+            Stmt::For { initializer, condition, increment, .. } => {
+                let initializer = initializer.as_ref().map_or(String::new(), |stmt| self.print_stmt(stmt));
+                let condition = condition.as_ref().map_or(String::new(), |expr| self.print(expr));
+                let increment = increment.as_ref().map_or(String::new(), |expr| self.print(expr));
+                format!("for ({}; {}; {})", initializer, condition, increment)
+            }
+            Stmt::Function { name, params, .. } => {
+                let params: Vec<&str> = params.iter().map(|param| param.lexeme.as_str()).collect();
+                format!("fun {}({})", name.lexeme, params.join(", "))
+            }
+            Stmt::Return { value: Some(value), .. } => format!("return {}", self.print(value)),
+            Stmt::Return { value: None, .. } => "return".to_string(),
+            Stmt::Break { value: Some(value), .. } => format!("break {}", self.print(value)),
+            Stmt::Break { value: None, .. } => "break".to_string(),
+            Stmt::Continue { .. } => "continue".to_string(),
+            Stmt::Empty => ";".to_string(),
+            Stmt::Import { path, alias: Some(alias), .. } => format!("import {} as {}", path.lexeme, alias.lexeme),
+            Stmt::Import { path, alias: None, .. } => format!("import {}", path.lexeme),
+            Stmt::Throw { value, .. } => format!("throw {}", self.print(value)),
+            Stmt::Try { name, .. } => format!("try catch ({})", name.lexeme),
+            Stmt::ForIn { name, iterable, .. } => format!("for {} in {}", name.lexeme, self.print(iterable)),
+            Stmt::Destructure { names, rest, initializer, .. } => {
+                let mut names: Vec<String> = names.iter().map(|name| name.lexeme.clone()).collect();
+                if let Some(rest) = rest {
+                    names.push(format!("...{}", rest.lexeme));
+                }
+                format!("var [{}] = {}", names.join(", "), self.print(initializer))
+            }
+        }
+    }
+
     fn parenthesize(&mut self, name: &str, exprs: &[&Expr]) -> String {
         let mut result = format!("({}", name);
         
@@ -25,6 +70,12 @@ impl AstPrinter {
     }
 }
 
+impl Default for AstPrinter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ExprVisitor<String> for AstPrinter {
     fn visit_binary_expr(&mut self, _expr: &Expr, left: &Expr, operator: &Token, right: &Expr) -> String {
         self.parenthesize(&operator.lexeme, &[left, right])
@@ -38,6 +89,7 @@ impl ExprVisitor<String> for AstPrinter {
         match value {
             Some(LiteralValue::String(s)) => s.clone(),
             Some(LiteralValue::Number(n)) => n.to_string(),
+            Some(LiteralValue::Int(n)) => n.to_string(),
             Some(LiteralValue::Boolean(b)) => b.to_string(),
             Some(LiteralValue::Nil) => "nil".to_string(),
             None => "nil".to_string(),
@@ -69,4 +121,50 @@ impl ExprVisitor<String> for AstPrinter {
         result.push(')');
         result
     }
-}
\ No newline at end of file
+
+    fn visit_loop_expr(&mut self, _expr: &Expr, _keyword: &Token, condition: &Expr, _body: &Stmt) -> String {
+        format!("(while {} ...)", condition.accept(self))
+    }
+
+    fn visit_range_expr(&mut self, _expr: &Expr, start: &Expr, operator: &Token, end: &Expr) -> String {
+        self.parenthesize(&operator.lexeme, &[start, end])
+    }
+
+    fn visit_let_expr(&mut self, _expr: &Expr, name: &Token, value: &Expr, body: &Expr) -> String {
+        format!("(let {} {} {})", name.lexeme, value.accept(self), body.accept(self))
+    }
+
+    fn visit_list_expr(&mut self, _expr: &Expr, elements: &[Expr]) -> String {
+        let elements: Vec<&Expr> = elements.iter().collect();
+        self.parenthesize("list", &elements)
+    }
+
+    fn visit_map_expr(&mut self, _expr: &Expr, _brace: &Token, entries: &[(Expr, Expr)]) -> String {
+        let mut result = "(map".to_string();
+        for (key, value) in entries {
+            result.push_str(&format!(" ({} {})", key.accept(self), value.accept(self)));
+        }
+        result.push(')');
+        result
+    }
+
+    fn visit_index_expr(&mut self, _expr: &Expr, object: &Expr, _bracket: &Token, index: &Expr) -> String {
+        self.parenthesize("index", &[object, index])
+    }
+
+    fn visit_index_set_expr(&mut self, _expr: &Expr, object: &Expr, _bracket: &Token, index: &Expr, value: &Expr) -> String {
+        self.parenthesize("index=", &[object, index, value])
+    }
+
+    fn visit_get_expr(&mut self, _expr: &Expr, object: &Expr, name: &Token) -> String {
+        format!("(. {} {})", object.accept(self), name.lexeme)
+    }
+
+    fn visit_set_expr(&mut self, _expr: &Expr, object: &Expr, name: &Token, value: &Expr) -> String {
+        format!("(.= {} {} {})", object.accept(self), name.lexeme, value.accept(self))
+    }
+
+    fn visit_optional_get_expr(&mut self, _expr: &Expr, object: &Expr, name: &Token) -> String {
+        format!("(?. {} {})", object.accept(self), name.lexeme)
+    }
+}
@@ -2,7 +2,7 @@
 
 // src/environment.rs
 use crate::value::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use anyhow::{Result, anyhow};
 /*
 Global Environment          ← Root of the chain
@@ -40,7 +40,7 @@ impl Environment {
 }
 
 // This is the "parking lot" that holds all environments
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EnvironmentArena {
     environments: Vec<Environment>, // All environments stored here
 }
@@ -66,6 +66,18 @@ impl EnvironmentArena {
         id
     }
 
+    // How many environments have been created. Nothing is freed yet, so this only grows.
+    pub fn len(&self) -> usize {
+        self.environments.len()
+    }
+
+    // A new environment with the same parent and a copy of the same variables, return its ID
+    pub fn duplicate(&mut self, env_id: EnvId) -> EnvId {
+        let id = self.environments.len();
+        self.environments.push(self.environments[env_id].clone());
+        id
+    }
+
     // Define a variable in a specific environment (by ID)
     pub fn define(&mut self, env_id: EnvId, name: String, value: Value) {
         self.environments[env_id].values.insert(name, value);
@@ -90,6 +102,109 @@ impl EnvironmentArena {
         }
     }
 
+    // Whether a variable exists in this environment or any of its parents, without building an error
+    pub fn is_defined(&self, env_id: EnvId, name: &str) -> bool {
+        let mut current = Some(env_id);
+        while let Some(env_id) = current {
+            if self.is_defined_local(env_id, name) {
+                return true;
+            }
+            current = self.environments[env_id].enclosing;
+        }
+        false
+    }
+
+    // Whether a variable exists directly in this environment, ignoring its parents
+    pub fn is_defined_local(&self, env_id: EnvId, name: &str) -> bool {
+        self.environments[env_id].values.contains_key(name)
+    }
+
+    // The outermost environment of a chain, i.e. the global scope the given environment lives in
+    pub fn root(&self, env_id: EnvId) -> EnvId {
+        let mut current = env_id;
+        while let Some(parent) = self.environments[current].enclosing {
+            current = parent;
+        }
+        current
+    }
+
+    // Every variable defined directly in one environment (not its parents), sorted by name
+    pub fn values(&self, env_id: EnvId) -> Vec<(String, Value)> {
+        let mut values: Vec<(String, Value)> = self.environments[env_id]
+            .values
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        values.sort_by(|a, b| a.0.cmp(&b.0));
+        values
+    }
+
+    // How many environments enclose this one. Globals are at depth 0.
+    pub fn depth(&self, env_id: EnvId) -> usize {
+        let mut depth = 0;
+        let mut current = env_id;
+        while let Some(parent) = self.environments[current].enclosing {
+            depth += 1;
+            current = parent;
+        }
+        depth
+    }
+
+    // Delete a variable from one environment (not its parents), handing back its old value
+    pub fn remove(&mut self, env_id: EnvId, name: &str) -> Option<Value> {
+        self.environments[env_id].values.remove(name)
+    }
+
+    /*
+    Every environment that can still be reached from the roots, for finding ones a GC could free.
+    An environment keeps alive its parents and whatever its values point at: a function's closure,
+    a module's globals, and anything inside lists and maps. A closure that captures itself
+    (fun counter() {...} defined in the env it closes over) is a cycle, so only the roots decide.
+    */
+    pub fn reachable_from(&self, roots: &[EnvId]) -> HashSet<EnvId> {
+        let mut reachable = HashSet::new();
+        let mut seen_containers = HashSet::new(); // Lists and maps can contain themselves
+        let mut pending: Vec<EnvId> = roots.to_vec();
+
+        while let Some(env_id) = pending.pop() {
+            if !reachable.insert(env_id) {
+                continue;
+            }
+            let environment = &self.environments[env_id];
+            pending.extend(environment.enclosing);
+            for value in environment.values.values() {
+                Self::closures_in(value, &mut pending, &mut seen_containers);
+            }
+        }
+        reachable
+    }
+
+    // Push every environment a value refers to onto `pending`
+    fn closures_in(value: &Value, pending: &mut Vec<EnvId>, seen_containers: &mut HashSet<usize>) {
+        match value {
+            Value::Function(function) => pending.push(function.closure()),
+            Value::Module(module) => pending.push(module.env),
+            Value::BoundNative(bound) => Self::closures_in(&bound.receiver, pending, seen_containers),
+            Value::List(list) if seen_containers.insert(list.as_ptr() as usize) => {
+                for element in list.borrow().iter() {
+                    Self::closures_in(element, pending, seen_containers);
+                }
+            }
+            Value::Map(map) if seen_containers.insert(map.as_ptr() as usize) => {
+                for element in map.borrow().values() {
+                    Self::closures_in(element, pending, seen_containers);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Like get(), but a missing variable reads as nil. That hides the difference between "var x;" and
+    // no x at all, so it's only for callers that don't care. is_defined() tells the two apart.
+    pub fn get_or_nil(&self, env_id: EnvId, name: &str) -> Value {
+        self.get(env_id, name).unwrap_or(Value::Nil)
+    }
+
     // Get a variable's value, walking up the chain if needed
     pub fn get(&self, env_id: EnvId, name: &str) -> Result<Value> {
         let mut current = env_id;
@@ -1,10 +1,44 @@
+use crate::interpreter::RuntimeError;
+use std::io::IsTerminal;
+
+// ANSI escape codes used when color is on
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
 pub struct ErrorReporter {
     had_error: bool,
+    had_runtime_error: bool,
+    source: Option<String>, // The code being parsed, so compile errors can show the offending line
+    color: bool,            // Color "Error" red and line numbers dim
 }
 
 impl ErrorReporter {
     pub fn new() -> Self {
-        Self { had_error: false }
+        Self { had_error: false, had_runtime_error: false, source: None, color: Self::detect_color() }
+    }
+
+    // Color only when a person is looking at stderr, and never when NO_COLOR is set (https://no-color.org)
+    fn detect_color() -> bool {
+        std::env::var_os("NO_COLOR").is_none() && std::io::stderr().is_terminal()
+    }
+
+    // Force color on or off instead of detecting it
+    pub fn set_color(&mut self, color: bool) {
+        self.color = color;
+    }
+
+    fn paint(&self, text: &str, style: &str) -> String {
+        if self.color {
+            format!("{}{}{}", style, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+
+    pub fn set_source(&mut self, source: &str) {
+        self.source = Some(source.to_string());
     }
 
     pub fn error(&mut self, line: usize, message: &str) {
@@ -12,15 +46,115 @@ impl ErrorReporter {
     }
 
     pub fn report(&mut self, line: usize, location: &str, message: &str) {
-        eprintln!("[line {}] Error{}: {}", line, location, message);
+        eprintln!("{}", self.format_report(line, location, message, None));
+        self.had_error = true;
+    }
+
+    // Like report(), but also prints the source line with the span [start, start + length) underlined
+    pub fn report_span(&mut self, line: usize, start: usize, length: usize, message: &str) {
+        eprintln!("{}", self.format_report(line, "", message, Some((start, length))));
         self.had_error = true;
     }
 
+    // Something suspicious but harmless, like an unused variable. The program still runs.
+    pub fn warning(&mut self, line: usize, message: &str) {
+        eprintln!("{}", self.format_warning(line, message));
+    }
+
+    pub fn format_warning(&self, line: usize, message: &str) -> String {
+        format!("{} {}: {}", self.paint(&format!("[line {}]", line), DIM), self.paint("Warning", YELLOW), message)
+    }
+
+    // Runtime errors don't make had_error true, they set had_runtime_error instead
+    pub fn runtime_error(&mut self, error: &RuntimeError) {
+        eprintln!("{}", self.format_runtime_error(error));
+        self.had_runtime_error = true;
+    }
+
+    pub fn format_runtime_error(&self, error: &RuntimeError) -> String {
+        let mut output = format!(
+            "{} {}: {}",
+            self.paint(&format!("[line {}]", error.token.line), DIM),
+            self.paint("Runtime Error", RED),
+            error.message
+        );
+        // Show the line from the code the token came from, not whatever source was set last: the error may
+        // be in an imported file or in a function from an earlier REPL input
+        if let Some(source) = &error.token.source
+            && let Some(snippet) = self.snippet(source, error.token.line, error.token.start, error.token.lexeme.len()) {
+            output.push('\n');
+            output.push_str(&snippet);
+        }
+        output
+    }
+
+    pub fn format_report(&self, line: usize, location: &str, message: &str, span: Option<(usize, usize)>) -> String {
+        let mut output = format!(
+            "{} {}{}: {}",
+            self.paint(&format!("[line {}]", line), DIM),
+            self.paint("Error", RED),
+            location,
+            message
+        );
+        if let Some((start, length)) = span
+            && let Some(source) = &self.source
+            && let Some(snippet) = self.snippet(source, line, start, length) {
+            output.push('\n');
+            output.push_str(&snippet);
+        }
+        output
+    }
+
+    /*
+    Renders the line containing `start`, with carets under the span:
+       2 | var a = 1 print a;
+         |           ^^^^^
+    */
+    fn snippet(&self, source: &str, line: usize, start: usize, length: usize) -> Option<String> {
+        if start > source.len() || !source.is_char_boundary(start) {
+            return None;
+        }
+
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[start..].find('\n').map_or(source.len(), |i| start + i);
+        let line_text = source[line_start..line_end].trim_end_matches('\r');
+
+        // Keep tabs as tabs so the carets line up however wide the terminal draws them
+        let padding: String = source[line_start..start]
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        // Count the characters the span starts in, so a span ending mid-character can't split it
+        let rest = &source[start..line_end];
+        let carets = "^".repeat(rest.char_indices().take_while(|(i, _)| *i < length).count().max(1));
+
+        let gutter = " ".repeat(line.to_string().len());
+        Some(format!(
+            "{} {}\n{} {}{}",
+            self.paint(&format!("{} |", line), DIM),
+            line_text,
+            self.paint(&format!("{} |", gutter), DIM),
+            padding,
+            self.paint(&carets, RED)
+        ))
+    }
+
     pub fn had_error(&self) -> bool {
         self.had_error
     }
 
+    pub fn had_runtime_error(&self) -> bool {
+        self.had_runtime_error
+    }
+
     pub fn reset(&mut self) {
         self.had_error = false;
+        self.had_runtime_error = false;
     }
-}
\ No newline at end of file
+}
+
+impl Default for ErrorReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,4 +1,5 @@
-use crate::token::{LiteralValue, Token};
+use crate::token::{LiteralValue, Token, TokenType};
+use crate::stmt::Stmt;
 
 // This will be your main expression enum
 #[derive(Debug, Clone)]
@@ -44,6 +45,52 @@ pub enum Expr {
         callee: Box<Expr>,
         paren: Token, // For error reporting ("line 5: wrong arity")
         arguments: Vec<Expr>
+    },
+    Loop { // A while loop used as a value: var x = while (true) { break 42; };
+        keyword: Token, // The "while", for error reporting
+        condition: Box<Expr>,
+        body: Box<Stmt>,
+    },
+    List { // [1, 2, 3]
+        elements: Vec<Expr>,
+    },
+    Map { // {"x": 1, "y": 2}
+        brace: Token, // For error reporting (non-string keys)
+        entries: Vec<(Expr, Expr)>,
+    },
+    Index { // list[0] or map["key"]
+        object: Box<Expr>,
+        bracket: Token, // The closing ']', for error reporting
+        index: Box<Expr>,
+    },
+    IndexSet { // list[0] = value. The object can be another index: a[0][1] = 5
+        object: Box<Expr>,
+        bracket: Token,
+        index: Box<Expr>,
+        value: Box<Expr>,
+    },
+    Get { // Property access with a dot, e.g. m.square for a module m
+        object: Box<Expr>,
+        name: Token,
+    },
+    Set { // m.x = 1, assigning to a property. The object is evaluated once, like for IndexSet.
+        object: Box<Expr>,
+        name: Token,
+        value: Box<Expr>,
+    },
+    OptionalGet { // a?.b is nil when a is nil, otherwise the same as a.b
+        object: Box<Expr>,
+        name: Token,
+    },
+    Range { // 1..10 (end excluded) or 1..=10 (end included)
+        start: Box<Expr>,
+        operator: Token, // ".." or "..=", also used for error reporting
+        end: Box<Expr>,
+    },
+    Let { // let x = 5 in x * x. x only exists inside the body.
+        name: Token,
+        value: Box<Expr>,
+        body: Box<Expr>,
     }
 }
 
@@ -57,6 +104,16 @@ pub trait ExprVisitor<T> {
     fn visit_assign_expr(&mut self, expr: &Expr, name: &Token, value: &Expr) -> T;
     fn visit_logical_expr(&mut self, expr: &Expr, left: &Expr, operator: &Token, right: &Expr) -> T;
     fn visit_call_expr(&mut self, expr: &Expr, callee: &Expr, paren: &Token, arguments: &[Expr]) -> T;
+    fn visit_loop_expr(&mut self, expr: &Expr, keyword: &Token, condition: &Expr, body: &Stmt) -> T;
+    fn visit_list_expr(&mut self, expr: &Expr, elements: &[Expr]) -> T;
+    fn visit_map_expr(&mut self, expr: &Expr, brace: &Token, entries: &[(Expr, Expr)]) -> T;
+    fn visit_index_expr(&mut self, expr: &Expr, object: &Expr, bracket: &Token, index: &Expr) -> T;
+    fn visit_index_set_expr(&mut self, expr: &Expr, object: &Expr, bracket: &Token, index: &Expr, value: &Expr) -> T;
+    fn visit_get_expr(&mut self, expr: &Expr, object: &Expr, name: &Token) -> T;
+    fn visit_set_expr(&mut self, expr: &Expr, object: &Expr, name: &Token, value: &Expr) -> T;
+    fn visit_range_expr(&mut self, expr: &Expr, start: &Expr, operator: &Token, end: &Expr) -> T;
+    fn visit_optional_get_expr(&mut self, expr: &Expr, object: &Expr, name: &Token) -> T;
+    fn visit_let_expr(&mut self, expr: &Expr, name: &Token, value: &Expr, body: &Expr) -> T;
 }
 
 // Visitor Pattern
@@ -91,6 +148,79 @@ impl Expr {
             Expr::Call { callee, paren, arguments } => {
                 visitor.visit_call_expr(self, callee, paren, arguments)
             }
+            Expr::Loop { keyword, condition, body } => {
+                visitor.visit_loop_expr(self, keyword, condition, body)
+            }
+            Expr::List { elements } => {
+                visitor.visit_list_expr(self, elements)
+            }
+            Expr::Map { brace, entries } => {
+                visitor.visit_map_expr(self, brace, entries)
+            }
+            Expr::Index { object, bracket, index } => {
+                visitor.visit_index_expr(self, object, bracket, index)
+            }
+            Expr::IndexSet { object, bracket, index, value } => {
+                visitor.visit_index_set_expr(self, object, bracket, index, value)
+            }
+            Expr::Get { object, name } => {
+                visitor.visit_get_expr(self, object, name)
+            }
+            Expr::Set { object, name, value } => {
+                visitor.visit_set_expr(self, object, name, value)
+            }
+            Expr::OptionalGet { object, name } => {
+                visitor.visit_optional_get_expr(self, object, name)
+            }
+            Expr::Range { start, operator, end } => {
+                visitor.visit_range_expr(self, start, operator, end)
+            }
+            Expr::Let { name, value, body } => {
+                visitor.visit_let_expr(self, name, value, body)
+            }
+        }
+    }
+
+    /*
+    True when evaluating the expression can have no effect at all: nothing is assigned, called, printed,
+    and it can't raise a runtime error either. So "1 + 2;" is pure, but "1 + nil;" is not, since it must still
+    fail with "Operands must be two numbers or two strings.". Being conservative is fine here:
+    anything we aren't sure about (variables, calls, division, ...) is simply not pure.
+    */
+    pub fn is_pure(&self) -> bool {
+        match self {
+            Expr::Literal { .. } => true,
+            Expr::Grouping { expression } => expression.is_pure(),
+            Expr::Unary { operator, right } => match operator.token_type {
+                TokenType::Bang => right.is_pure(),
+                _ => right.is_numeric_constant(),
+            },
+            Expr::Binary { left, operator, right } => match operator.token_type {
+                TokenType::EqualEqual | TokenType::BangEqual => left.is_pure() && right.is_pure(),
+                TokenType::Plus | TokenType::Minus | TokenType::Star | TokenType::StarStar
+                | TokenType::Greater | TokenType::GreaterEqual | TokenType::Less | TokenType::LessEqual => {
+                    left.is_numeric_constant() && right.is_numeric_constant()
+                }
+                _ => false, // "/" can divide by zero
+            },
+            Expr::Logical { left, right, .. } => left.is_pure() && right.is_pure(),
+            Expr::List { elements } => elements.iter().all(Expr::is_pure),
+            _ => false,
+        }
+    }
+
+    // A number, or arithmetic on numbers, that can't fail. Int overflow just falls back to a float.
+    fn is_numeric_constant(&self) -> bool {
+        match self {
+            Expr::Literal { value } => matches!(value, Some(LiteralValue::Number(_)) | Some(LiteralValue::Int(_))),
+            Expr::Grouping { expression } => expression.is_numeric_constant(),
+            Expr::Unary { operator, right } => operator.token_type == TokenType::Minus && right.is_numeric_constant(),
+            Expr::Binary { left, operator, right } => {
+                matches!(operator.token_type, TokenType::Plus | TokenType::Minus | TokenType::Star | TokenType::StarStar)
+                    && left.is_numeric_constant()
+                    && right.is_numeric_constant()
+            }
+            _ => false,
         }
     }
 
@@ -141,4 +271,49 @@ impl Expr {
     pub fn call(callee: Expr, paren: Token, arguments: Vec<Expr>) -> Self {
         Expr::Call { callee: Box::new(callee), paren, arguments }
     }
-}
\ No newline at end of file
+
+    pub fn loop_expr(keyword: Token, condition: Expr, body: Stmt) -> Self {
+        Expr::Loop { keyword, condition: Box::new(condition), body: Box::new(body) }
+    }
+
+    pub fn list(elements: Vec<Expr>) -> Self {
+        Expr::List { elements }
+    }
+
+    pub fn map(brace: Token, entries: Vec<(Expr, Expr)>) -> Self {
+        Expr::Map { brace, entries }
+    }
+
+    pub fn index(object: Expr, bracket: Token, index: Expr) -> Self {
+        Expr::Index { object: Box::new(object), bracket, index: Box::new(index) }
+    }
+
+    pub fn index_set(object: Expr, bracket: Token, index: Expr, value: Expr) -> Self {
+        Expr::IndexSet {
+            object: Box::new(object),
+            bracket,
+            index: Box::new(index),
+            value: Box::new(value),
+        }
+    }
+
+    pub fn get(object: Expr, name: Token) -> Self {
+        Expr::Get { object: Box::new(object), name }
+    }
+
+    pub fn set(object: Expr, name: Token, value: Expr) -> Self {
+        Expr::Set { object: Box::new(object), name, value: Box::new(value) }
+    }
+
+    pub fn optional_get(object: Expr, name: Token) -> Self {
+        Expr::OptionalGet { object: Box::new(object), name }
+    }
+
+    pub fn range(start: Expr, operator: Token, end: Expr) -> Self {
+        Expr::Range { start: Box::new(start), operator, end: Box::new(end) }
+    }
+
+    pub fn let_expr(name: Token, value: Expr, body: Expr) -> Self {
+        Expr::Let { name, value: Box::new(value), body: Box::new(body) }
+    }
+}
@@ -0,0 +1,80 @@
+/*
+Constant folding: a pass over a parsed expression that replaces literal-only arithmetic with its result.
+
+  2 + 3 * 4    becomes  14
+  x + 2 * 3    becomes  (+ x 6)
+  1 / 0        stays as it is, so the "Division by zero." error still happens at runtime
+
+Subtrees are evaluated with the real interpreter, so folding can't disagree with running the code.
+Anything that fails to evaluate is simply left unfolded.
+*/
+
+use crate::expr::Expr;
+use crate::interpreter::Interpreter;
+use crate::token::LiteralValue;
+use crate::value::Value;
+
+pub fn fold_constants(expr: Expr) -> Expr {
+    Folder { interpreter: Interpreter::new() }.fold(expr)
+}
+
+struct Folder {
+    interpreter: Interpreter, // Scratch interpreter for evaluating literal subtrees
+}
+
+impl Folder {
+    fn fold(&mut self, expr: Expr) -> Expr {
+        match expr {
+            Expr::Binary { left, operator, right } => {
+                let (left, right) = (self.fold(*left), self.fold(*right));
+                let all_literal = is_literal(&left) && is_literal(&right);
+                self.evaluate_if(all_literal, Expr::binary(left, operator, right))
+            }
+            Expr::Logical { left, operator, right } => {
+                let (left, right) = (self.fold(*left), self.fold(*right));
+                let all_literal = is_literal(&left) && is_literal(&right);
+                self.evaluate_if(all_literal, Expr::logical(left, operator, right))
+            }
+            Expr::Unary { operator, right } => {
+                let right = self.fold(*right);
+                let all_literal = is_literal(&right);
+                self.evaluate_if(all_literal, Expr::unary(operator, right))
+            }
+            Expr::Grouping { expression } => {
+                let expression = self.fold(*expression);
+                if is_literal(&expression) {
+                    expression // (14) is just 14
+                } else {
+                    Expr::grouping(expression)
+                }
+            }
+            // Variables, calls, assignments, ... are left untouched
+            other => other,
+        }
+    }
+
+    fn evaluate_if(&mut self, all_literal: bool, expr: Expr) -> Expr {
+        if !all_literal {
+            return expr;
+        }
+        match expr.accept(&mut self.interpreter).ok().and_then(to_literal) {
+            Some(literal) => Expr::literal(Some(literal)),
+            None => expr,
+        }
+    }
+}
+
+fn is_literal(expr: &Expr) -> bool {
+    matches!(expr, Expr::Literal { .. })
+}
+
+fn to_literal(value: Value) -> Option<LiteralValue> {
+    match value {
+        Value::Int(n) => Some(LiteralValue::Int(n)),
+        Value::Number(n) => Some(LiteralValue::Number(n)),
+        Value::String(s) => Some(LiteralValue::String(s)),
+        Value::Boolean(b) => Some(LiteralValue::Boolean(b)),
+        Value::Nil => Some(LiteralValue::Nil),
+        _ => None,
+    }
+}
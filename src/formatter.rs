@@ -0,0 +1,292 @@
+/*
+Turns a parsed program back into Lox source, laid out the same way every time (lox fmt file.lox):
+  var x=1+2;if(x>2){print x;}
+becomes
+  var x = 1 + 2;
+  if (x > 2) {
+    print x;
+  }
+
+One statement per line, two spaces of indentation per block, spaces around binary operators.
+Parentheses are kept exactly where the source had them (the parser keeps them as Grouping nodes).
+Comments aren't in the AST, so they are lost, and raw strings come back as normal escaped strings.
+Unlike AstPrinter, the output is meant to be read back: formatting it again gives the same text.
+*/
+
+use crate::expr::{Expr, ExprVisitor};
+use crate::stmt::{Stmt, StmtVisitor};
+use crate::token::{LiteralValue, Token};
+
+pub fn format_program(statements: &[Stmt]) -> String {
+    let mut formatter = Formatter::new();
+    let mut output = String::new();
+    for statement in statements {
+        output.push_str(&statement.accept(&mut formatter));
+        output.push('\n');
+    }
+    output
+}
+
+pub struct Formatter {
+    indent: usize, // Blocks we're inside of
+}
+
+impl Formatter {
+    pub fn new() -> Self {
+        Self { indent: 0 }
+    }
+
+    fn padding(&self) -> String {
+        "  ".repeat(self.indent)
+    }
+
+    // "{", each statement on its own line one level deeper, then "}" lined up with the line that opened it
+    fn block(&mut self, statements: &[Stmt]) -> String {
+        if statements.is_empty() {
+            return "{}".to_string();
+        }
+        self.indent += 1;
+        let lines: Vec<String> = statements
+            .iter()
+            .map(|statement| format!("{}{}", self.padding(), statement.accept(self)))
+            .collect();
+        self.indent -= 1;
+        format!("{{\n{}\n{}}}", lines.join("\n"), self.padding())
+    }
+
+    // The body of an if, loop or function. A block opens on the same line; anything else follows after a space.
+    fn body(&mut self, body: &Stmt) -> String {
+        match body {
+            Stmt::Block { statements } => self.block(statements),
+            other => other.accept(self),
+        }
+    }
+
+    fn list(&mut self, exprs: &[Expr]) -> String {
+        let parts: Vec<String> = exprs.iter().map(|expr| expr.accept(self)).collect();
+        parts.join(", ")
+    }
+}
+
+impl Default for Formatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn names(tokens: &[Token]) -> String {
+    let names: Vec<&str> = tokens.iter().map(|token| token.lexeme.as_str()).collect();
+    names.join(", ")
+}
+
+// Written so the scanner reads back the same value: 2.0 stays a float and doesn't turn into the int 2
+fn literal(value: &Option<LiteralValue>) -> String {
+    match value {
+        Some(LiteralValue::String(s)) => {
+            let mut quoted = String::from("\"");
+            for c in s.chars() {
+                match c {
+                    '\n' => quoted.push_str("\\n"),
+                    '\t' => quoted.push_str("\\t"),
+                    '\r' => quoted.push_str("\\r"),
+                    '"' => quoted.push_str("\\\""),
+                    '\\' => quoted.push_str("\\\\"),
+                    c => quoted.push(c),
+                }
+            }
+            quoted.push('"');
+            quoted
+        }
+        Some(LiteralValue::Number(n)) if n.is_nan() => "NaN".to_string(),
+        Some(LiteralValue::Number(n)) if n.is_infinite() => "Infinity".to_string(), // Literals are never negative
+        Some(LiteralValue::Number(n)) if n.fract() == 0.0 => format!("{:.1}", n),
+        Some(LiteralValue::Number(n)) => n.to_string(),
+        Some(LiteralValue::Int(n)) => n.to_string(),
+        Some(LiteralValue::Boolean(b)) => b.to_string(),
+        Some(LiteralValue::Nil) | None => "nil".to_string(),
+    }
+}
+
+impl StmtVisitor<String> for Formatter {
+    fn visit_expression_stmt(&mut self, _stmt: &Stmt, expression: &Expr) -> String {
+        format!("{};", expression.accept(self))
+    }
+
+    fn visit_print_stmt(&mut self, _stmt: &Stmt, expressions: &[Expr]) -> String {
+        format!("print {};", self.list(expressions))
+    }
+
+    fn visit_var_stmt(&mut self, _stmt: &Stmt, name: &Token, initializer: &Option<Box<Expr>>) -> String {
+        match initializer {
+            Some(initializer) => format!("var {} = {};", name.lexeme, initializer.accept(self)),
+            None => format!("var {};", name.lexeme),
+        }
+    }
+
+    fn visit_block_stmt(&mut self, _stmt: &Stmt, statements: Vec<Stmt>) -> String {
+        self.block(&statements)
+    }
+
+    // else if chains stay flat: "} else if (...) {"
+    fn visit_if_stmt(&mut self, _stmt: &Stmt, condition: &Expr, then_branch: &Stmt, else_branch: &Option<Box<Stmt>>) -> String {
+        let mut output = format!("if ({}) {}", condition.accept(self), self.body(then_branch));
+        if let Some(else_branch) = else_branch {
+            output.push_str(" else ");
+            output.push_str(&self.body(else_branch));
+        }
+        output
+    }
+
+    fn visit_while_stmt(&mut self, _stmt: &Stmt, _keyword: &Token, condition: &Expr, body: &Stmt) -> String {
+        format!("while ({}) {}", condition.accept(self), self.body(body))
+    }
+
+    // for (var i = 0; i < 3; i = i + 1), or for (;;) with every clause left out
+    fn visit_for_stmt(&mut self, _stmt: &Stmt, _keyword: &Token, initializer: Option<&Stmt>, condition: Option<&Expr>,
+                      increment: Option<&Expr>, body: &Stmt) -> String {
+        let mut clauses = initializer.map_or(";".to_string(), |initializer| initializer.accept(self));
+        if let Some(condition) = condition {
+            clauses.push(' ');
+            clauses.push_str(&condition.accept(self));
+        }
+        clauses.push(';');
+        if let Some(increment) = increment {
+            clauses.push(' ');
+            clauses.push_str(&increment.accept(self));
+        }
+        format!("for ({}) {}", clauses, self.body(body))
+    }
+
+    fn visit_function_stmt(&mut self, _stmt: &Stmt, name: &Token, params: &[Token], body: &[Stmt]) -> String {
+        format!("fun {}({}) {}", name.lexeme, names(params), self.block(body))
+    }
+
+    fn visit_return_stmt(&mut self, _stmt: &Stmt, _keyword: &Token, value: &Option<Box<Expr>>) -> String {
+        match value {
+            Some(value) => format!("return {};", value.accept(self)),
+            None => "return;".to_string(),
+        }
+    }
+
+    fn visit_break_stmt(&mut self, _stmt: &Stmt, _keyword: &Token, value: &Option<Box<Expr>>) -> String {
+        match value {
+            Some(value) => format!("break {};", value.accept(self)),
+            None => "break;".to_string(),
+        }
+    }
+
+    fn visit_continue_stmt(&mut self, _stmt: &Stmt, _keyword: &Token) -> String {
+        "continue;".to_string()
+    }
+
+    fn visit_empty_stmt(&mut self, _stmt: &Stmt) -> String {
+        ";".to_string()
+    }
+
+    // The path token is the string as written, quotes included
+    fn visit_import_stmt(&mut self, _stmt: &Stmt, _keyword: &Token, path: &Token, alias: &Option<Token>) -> String {
+        match alias {
+            Some(alias) => format!("import {} as {};", path.lexeme, alias.lexeme),
+            None => format!("import {};", path.lexeme),
+        }
+    }
+
+    fn visit_for_in_stmt(&mut self, _stmt: &Stmt, name: &Token, iterable: &Expr, body: &Stmt) -> String {
+        format!("for ({} in {}) {}", name.lexeme, iterable.accept(self), self.body(body))
+    }
+
+    fn visit_destructure_stmt(&mut self, _stmt: &Stmt, _bracket: &Token, names: &[Token], rest: &Option<Token>, initializer: &Expr) -> String {
+        let mut pattern: Vec<String> = names.iter().map(|name| name.lexeme.clone()).collect();
+        if let Some(rest) = rest {
+            pattern.push(format!("...{}", rest.lexeme));
+        }
+        format!("var [{}] = {};", pattern.join(", "), initializer.accept(self))
+    }
+
+    fn visit_try_stmt(&mut self, _stmt: &Stmt, body: &[Stmt], name: &Token, handler: &[Stmt]) -> String {
+        format!("try {} catch ({}) {}", self.block(body), name.lexeme, self.block(handler))
+    }
+
+    fn visit_throw_stmt(&mut self, _stmt: &Stmt, _keyword: &Token, value: &Expr) -> String {
+        format!("throw {};", value.accept(self))
+    }
+}
+
+impl ExprVisitor<String> for Formatter {
+    fn visit_binary_expr(&mut self, _expr: &Expr, left: &Expr, operator: &Token, right: &Expr) -> String {
+        format!("{} {} {}", left.accept(self), operator.lexeme, right.accept(self))
+    }
+
+    fn visit_unary_expr(&mut self, _expr: &Expr, operator: &Token, right: &Expr) -> String {
+        format!("{}{}", operator.lexeme, right.accept(self))
+    }
+
+    fn visit_literal_expr(&mut self, _expr: &Expr, value: &Option<LiteralValue>) -> String {
+        literal(value)
+    }
+
+    fn visit_grouping_expr(&mut self, _expr: &Expr, expression: &Expr) -> String {
+        format!("({})", expression.accept(self))
+    }
+
+    fn visit_variable_expr(&mut self, _expr: &Expr, name: &Token) -> String {
+        name.lexeme.clone()
+    }
+
+    fn visit_assign_expr(&mut self, _expr: &Expr, name: &Token, value: &Expr) -> String {
+        format!("{} = {}", name.lexeme, value.accept(self))
+    }
+
+    // Keeps the spelling that was used, "and" or "&&"
+    fn visit_logical_expr(&mut self, _expr: &Expr, left: &Expr, operator: &Token, right: &Expr) -> String {
+        format!("{} {} {}", left.accept(self), operator.lexeme, right.accept(self))
+    }
+
+    fn visit_call_expr(&mut self, _expr: &Expr, callee: &Expr, _paren: &Token, arguments: &[Expr]) -> String {
+        format!("{}({})", callee.accept(self), self.list(arguments))
+    }
+
+    fn visit_loop_expr(&mut self, _expr: &Expr, _keyword: &Token, condition: &Expr, body: &Stmt) -> String {
+        format!("while ({}) {}", condition.accept(self), self.body(body))
+    }
+
+    fn visit_list_expr(&mut self, _expr: &Expr, elements: &[Expr]) -> String {
+        format!("[{}]", self.list(elements))
+    }
+
+    fn visit_map_expr(&mut self, _expr: &Expr, _brace: &Token, entries: &[(Expr, Expr)]) -> String {
+        let entries: Vec<String> = entries
+            .iter()
+            .map(|(key, value)| format!("{}: {}", key.accept(self), value.accept(self)))
+            .collect();
+        format!("{{{}}}", entries.join(", "))
+    }
+
+    fn visit_index_expr(&mut self, _expr: &Expr, object: &Expr, _bracket: &Token, index: &Expr) -> String {
+        format!("{}[{}]", object.accept(self), index.accept(self))
+    }
+
+    fn visit_index_set_expr(&mut self, _expr: &Expr, object: &Expr, _bracket: &Token, index: &Expr, value: &Expr) -> String {
+        format!("{}[{}] = {}", object.accept(self), index.accept(self), value.accept(self))
+    }
+
+    fn visit_get_expr(&mut self, _expr: &Expr, object: &Expr, name: &Token) -> String {
+        format!("{}.{}", object.accept(self), name.lexeme)
+    }
+
+    fn visit_set_expr(&mut self, _expr: &Expr, object: &Expr, name: &Token, value: &Expr) -> String {
+        format!("{}.{} = {}", object.accept(self), name.lexeme, value.accept(self))
+    }
+
+    fn visit_optional_get_expr(&mut self, _expr: &Expr, object: &Expr, name: &Token) -> String {
+        format!("{}?.{}", object.accept(self), name.lexeme)
+    }
+
+    fn visit_range_expr(&mut self, _expr: &Expr, start: &Expr, operator: &Token, end: &Expr) -> String {
+        format!("{}{}{}", start.accept(self), operator.lexeme, end.accept(self))
+    }
+
+    fn visit_let_expr(&mut self, _expr: &Expr, name: &Token, value: &Expr, body: &Expr) -> String {
+        format!("let {} = {} in {}", name.lexeme, value.accept(self), body.accept(self))
+    }
+}
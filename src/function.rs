@@ -1,11 +1,11 @@
 use crate::stmt::Stmt;
 use crate::token::Token;
-use crate::environment::Environment;
 use crate::environment::EnvId;
+use std::rc::Rc;
 
 #[derive(Debug, Clone)]
 pub struct LoxFunction {
-    declaration: FunctionDeclaration,
+    declaration: Rc<FunctionDeclaration>, // Shared by every copy of this function value, see PartialEq
     closure: EnvId, // Capture the environment at declaration time
 }
 
@@ -18,7 +18,7 @@ pub struct FunctionDeclaration {
 
 impl LoxFunction {
     pub fn new(declaration: FunctionDeclaration, closure: EnvId) -> Self {
-        Self { declaration, closure }
+        Self { declaration: Rc::new(declaration), closure }
     }
     
     pub fn arity(&self) -> usize {
@@ -29,6 +29,13 @@ impl LoxFunction {
         &self.declaration.name.lexeme
     }
     
+    // "<fn add/2>": like printing the function, plus how many arguments it takes. repr() shows this.
+    // A function without a name shows as "<fn anonymous/2>".
+    pub fn signature(&self) -> String {
+        let name = if self.name().is_empty() { "anonymous" } else { self.name() };
+        format!("<fn {}/{}>", name, self.arity())
+    }
+
     pub fn declaration(&self) -> &FunctionDeclaration {
         &self.declaration
     }
@@ -38,8 +45,11 @@ impl LoxFunction {
     }
 }
 
+// Functions are equal only when they are the same function: copies of the value made when one "fun" ran,
+// closing over the same environment. Two counters from one makeCounter() are different, even with the same
+// name and code, and so are two functions declared at the same spot in different REPL lines or files.
 impl PartialEq for LoxFunction {
     fn eq(&self, other: &Self) -> bool {
-        self.declaration.name.lexeme == other.declaration.name.lexeme
+        Rc::ptr_eq(&self.declaration, &other.declaration) && self.closure == other.closure
     }
 }
\ No newline at end of file
@@ -0,0 +1,59 @@
+/*
+REPL history, kept in a plain text file with one entered line per line (~/.lox_history by default).
+The file is loaded when the REPL starts, and every line is appended as soon as it is entered,
+so nothing is lost if the REPL is killed. There's no line editor yet, so recalling lines with the
+arrow keys isn't possible; entries() is there for when there is one.
+*/
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+pub struct History {
+    path: Option<PathBuf>, // None once writing failed, or when history is off
+    entries: Vec<String>,
+}
+
+impl History {
+    // Load the history in `path`. A missing or unreadable file just means no history yet.
+    pub fn open(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let entries = fs::read_to_string(&path)
+            .map(|text| text.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+        Self { path: Some(path), entries }
+    }
+
+    // History that is never saved
+    pub fn disabled() -> Self {
+        Self { path: None, entries: Vec::new() }
+    }
+
+    // ~/.lox_history, if we know where home is
+    pub fn default_path() -> Option<PathBuf> {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".lox_history"))
+    }
+
+    // Remember a line and append it to the file. Blank lines are skipped.
+    // On a write error the line is still kept in memory, and the caller can decide to disable() saving.
+    pub fn add(&mut self, line: &str) -> io::Result<()> {
+        let line = line.trim_end_matches(['\n', '\r']);
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+        self.entries.push(line.to_string());
+
+        let Some(path) = &self.path else { return Ok(()) };
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", line)
+    }
+
+    // Stop writing to the file, e.g. after it turned out to be unwritable
+    pub fn disable(&mut self) {
+        self.path = None;
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+}
@@ -8,39 +8,174 @@ Walks the tree using visitor pattern and executes
 
 use crate::expr::{Expr, ExprVisitor};
 use crate::stmt::{Stmt, StmtVisitor};
-use crate::environment::{Environment, EnvId, EnvironmentArena};
+use crate::environment::{EnvId, EnvironmentArena};
 use crate::token::{Token, TokenType, LiteralValue};
-use crate::value::Value;
+use crate::value::{LoxModule, Value, WithPrecision};
+use std::rc::Rc;
 use anyhow::{anyhow, Result};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 use crate::function::{LoxFunction, FunctionDeclaration};
-use crate::native::NativeFunction;
+use crate::native::{Arity, BoundNative, ClockSource, HostFunction, NativeFunction, SystemClock};
+use crate::scanner::Scanner;
+use crate::parser::Parser;
+use crate::error::ErrorReporter;
+use crate::ast_printer::AstPrinter;
+use std::io::Write;
 
 pub struct Interpreter {
     arena: EnvironmentArena,  // The "parking lot" for all environments
     globals: EnvId,           // ID of global environment
     environment: EnvId,       // ID of current environment
+    profiling_enabled: bool,  // Count node visits only when asked, so normal runs don't pay for it
+    profile: BTreeMap<&'static str, usize>, // Visits per node kind, e.g. "call_expr" -> 3
+    unwinding_value: Option<Value>, // The value of the "return"/"break" currently unwinding the stack
+    script_path: Option<PathBuf>,   // File being run, imports are resolved relative to it
+    importing: HashSet<PathBuf>,    // Files whose import is still running, to catch circular imports
+    precision: Option<usize>,       // Decimals shown for non-integer numbers, None = shortest round-trip
+    trace: Option<Box<dyn Write>>,  // Where --trace logs each statement and expression value, None = off
+    max_iterations: usize,          // Loop iterations allowed in total, 0 = no limit
+    iterations: usize,              // Loop iterations run so far, checked against max_iterations
+    clock: Box<dyn ClockSource>,    // What clock() reads
+    call_depth: usize,              // Lox function calls in progress, 0 = running top-level code
+    repl_mode: bool,                // In the REPL a top-level "return" just ends the line instead of failing
+    eval_depth: usize,              // eval() calls in progress, see eval_nested()
+}
+
+// eval() calls that can be in progress at once. Deeper than this is almost surely a function eval-ing itself.
+const MAX_EVAL_DEPTH: usize = 32;
+
+/*
+A saved copy of the interpreter's variables, from Interpreter::snapshot().
+It holds a clone of the whole arena, so taking one costs time and memory proportional to every
+environment created so far (including finished function calls). Lists and maps are shared through Rc,
+so restoring brings back which list a variable holds, but not the list's old contents.
+*/
+pub struct InterpreterSnapshot {
+    arena: EnvironmentArena,
+    environment: EnvId,
 }
 
 #[derive(Debug)]
 pub struct RuntimeError {
     pub token: Token,
     pub message: String,
+    pub thrown: bool, // Raised by a "throw" in the script, not by the interpreter itself
+    kind: RuntimeErrorKind,
 }
 
+// What sort of runtime error it was, for tools that want more than the message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeErrorKind {
+    TypeError,         // A value of the wrong type, like 1 + nil or indexing a number
+    UndefinedVariable,
+    UndefinedProperty, // A missing module member, string method or map key
+    DivisionByZero,
+    Arity,             // A call with the wrong number of arguments
+    IndexOutOfRange,
+    Destructure,       // var [a, b] = ... with the wrong number of elements
+    Import,            // A module that can't be read or parsed, or a circular import
+    IterationLimit,    // Hit the limit from set_max_iterations()
+    Thrown,            // A "throw" in the script
+    Native,            // Raised inside a native or host function, like a bad argument to len()
+    Other,
+}
+
+impl RuntimeError {
+    pub fn new(kind: RuntimeErrorKind, token: &Token, message: impl Into<String>) -> Self {
+        Self { token: token.clone(), message: message.into(), thrown: false, kind }
+    }
+
+    // throw "oops"; the message is the thrown value as a string
+    pub fn from_throw(keyword: &Token, message: impl Into<String>) -> Self {
+        Self { thrown: true, ..Self::new(RuntimeErrorKind::Thrown, keyword, message) }
+    }
+
+    pub fn undefined_variable(name: &Token) -> Self {
+        Self::new(RuntimeErrorKind::UndefinedVariable, name, format!("Undefined variable '{}'.", name.lexeme))
+    }
+
+    pub fn division_by_zero(operator: &Token) -> Self {
+        Self::new(RuntimeErrorKind::DivisionByZero, operator, "Division by zero.")
+    }
+
+    pub fn kind(&self) -> RuntimeErrorKind {
+        self.kind
+    }
+
+    pub fn line(&self) -> usize {
+        self.token.line
+    }
+
+    // 1-based, or 0 for tokens that weren't made by the scanner
+    pub fn column(&self) -> usize {
+        self.token.column
+    }
+}
+
+// Why call_value() failed before the callee even ran. at_call_site() turns it into a RuntimeError of that kind.
 #[derive(Debug)]
-pub struct ReturnValue {
-    pub value: Value,
+struct CallError {
+    kind: RuntimeErrorKind,
+    message: String,
 }
 
+impl std::fmt::Display for CallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for CallError {}
+
+// anyhow errors must be Send + Sync, but a Value may hold an Rc (lists, maps).
+// So the returned value itself waits in Interpreter::unwinding_value until the signal is caught.
+#[derive(Debug)]
+pub struct ReturnValue;
+
+// Raised by "break" and caught by the nearest enclosing loop
+#[derive(Debug)]
+pub struct BreakSignal;
+
+// Raised by "continue". The loop catches it and goes on with its next iteration.
+#[derive(Debug)]
+pub struct ContinueSignal;
+
 impl std::fmt::Display for RuntimeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "[line {}] Runtime Error: {}", self.token.line, self.message)
     }
 }
 
+// Added by run_statements() on top of the error from a failing top-level statement, to say which one it was.
+// The original error is still there: err.downcast_ref::<RuntimeError>() works as before.
+#[derive(Debug)]
+pub struct StatementFailed {
+    pub index: usize, // 0-based position in the statements passed to run_statements()
+}
+
+impl std::fmt::Display for StatementFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Statement {} failed", self.index)
+    }
+}
+
 impl std::fmt::Display for ReturnValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Return: {}", self.value)
+        write!(f, "Return")
+    }
+}
+
+impl std::fmt::Display for BreakSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Break")
+    }
+}
+
+impl std::fmt::Display for ContinueSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Continue")
     }
 }
 
@@ -48,19 +183,59 @@ impl std::error::Error for RuntimeError {}
 // We implement error typeclass to ReturnValue because we want "?" to immediately exit the execution.
 // Since "return" should stop executing the remaining statements in the function.
 impl std::error::Error for ReturnValue {}
+// Same trick for "break": it unwinds out of the loop body until visit_while_stmt catches it.
+impl std::error::Error for BreakSignal {}
+impl std::error::Error for ContinueSignal {}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl Interpreter {
     pub fn new() -> Self {
         let mut arena = EnvironmentArena::new();
         let globals = arena.create_env(); // Create global environment, get its ID
         
-        // Define native functions in the global environment
-        arena.define(globals, "clock".to_string(), Value::NativeFunction(NativeFunction::Clock));
-        
-        Self {
+        let mut interpreter = Self {
             arena,
             globals,
             environment: globals, // Start in global scope
+            profiling_enabled: false,
+            profile: BTreeMap::new(),
+            unwinding_value: None,
+            script_path: None,
+            importing: HashSet::new(),
+            precision: None,
+            trace: None,
+            max_iterations: 0,
+            iterations: 0,
+            clock: Box::new(SystemClock),
+            call_depth: 0,
+            repl_mode: false,
+            eval_depth: 0,
+        };
+        interpreter.define_natives(interpreter.globals);
+        interpreter
+    }
+
+    // An interpreter whose clock() reads `clock` instead of the real time
+    pub fn with_clock(clock: impl ClockSource + 'static) -> Self {
+        let mut interpreter = Self::new();
+        interpreter.clock = Box::new(clock);
+        interpreter
+    }
+
+    // Seconds since the epoch, according to the clock source
+    pub fn now(&self) -> f64 {
+        self.clock.now()
+    }
+
+    // Define native functions in a global environment (the main one, or a module's)
+    fn define_natives(&mut self, env: EnvId) {
+        for native in NativeFunction::ALL {
+            self.arena.define(env, native.name().to_string(), Value::NativeFunction(native.clone()));
         }
     }
     
@@ -68,11 +243,277 @@ impl Interpreter {
         // TODO: Execute each statement
         // Handle runtime errors gracefully
         for statement in statements {
-            statement.accept(self)?;
+            if !self.execute_top_level(statement)? {
+                break;
+            }
         }
         Ok(())
     }
 
+    // Like interpret(), but says how far it got: Ok with the number of statements run, or the error with a
+    // StatementFailed context holding the index of the statement that failed.
+    pub fn run_statements(&mut self, statements: &[Stmt]) -> Result<usize> {
+        for (index, statement) in statements.iter().enumerate() {
+            match self.execute_top_level(statement) {
+                Ok(true) => {}
+                Ok(false) => return Ok(index + 1), // a REPL "return" ran, and skips the rest
+                Err(err) => return Err(err.context(StatementFailed { index })),
+            }
+        }
+        Ok(statements.len())
+    }
+
+    // Run one top-level statement. False means a "return" (only allowed in REPL mode) skips the rest of the input.
+    fn execute_top_level(&mut self, statement: &Stmt) -> Result<bool> {
+        match self.execute(statement) {
+            Ok(()) => Ok(true),
+            Err(err) if err.is::<ReturnValue>() => {
+                self.unwinding_value = None;
+                Ok(false)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    // The REPL sets this so a stray top-level "return" is ignored. Scripts get a runtime error instead.
+    pub fn set_repl_mode(&mut self, repl: bool) {
+        self.repl_mode = repl;
+    }
+
+    // Tell the interpreter which file it is running, so "import" can find files next to it
+    pub fn set_script_path(&mut self, path: impl AsRef<Path>) {
+        self.script_path = Some(path.as_ref().to_path_buf());
+    }
+
+    // Log every statement before it runs and every expression's value to `output`, indented by scope depth
+    pub fn enable_trace(&mut self, output: impl Write + 'static) {
+        self.trace = Some(Box::new(output));
+    }
+
+    fn trace(&mut self, text: String) {
+        let indent = "  ".repeat(self.arena.depth(self.environment));
+        if let Some(output) = self.trace.as_mut() {
+            // Tracing is best effort, a closed stderr shouldn't stop the program
+            let _ = writeln!(output, "{}{}", indent, text);
+        }
+    }
+
+    // Every statement runs through here and every expression through evaluate(), so --trace sees them all
+    fn execute(&mut self, stmt: &Stmt) -> Result<()> {
+        if self.trace.is_some() {
+            let text = AstPrinter::new().print_stmt(stmt);
+            self.trace(format!("[stmt] {}", text));
+        }
+        stmt.accept(self)
+    }
+
+    fn evaluate(&mut self, expr: &Expr) -> Result<Value> {
+        let value = expr.accept(self)?;
+        if self.trace.is_some() {
+            let text = format!("[expr] {} => {}", AstPrinter::new().print(expr), self.stringify(&value));
+            self.trace(text);
+        }
+        Ok(value)
+    }
+
+    pub fn enable_profiling(&mut self) {
+        self.profiling_enabled = true;
+    }
+
+    // How many times each kind of node was evaluated, sorted by node kind
+    pub fn profile(&self) -> &BTreeMap<&'static str, usize> {
+        &self.profile
+    }
+
+    pub fn snapshot(&self) -> InterpreterSnapshot {
+        InterpreterSnapshot {
+            arena: self.arena.clone(),
+            environment: self.environment,
+        }
+    }
+
+    // Roll every variable back to how it was when the snapshot was taken
+    pub fn restore(&mut self, snapshot: InterpreterSnapshot) {
+        self.arena = snapshot.arena;
+        self.environment = snapshot.environment;
+    }
+
+    // The environment code is running in right now: the globals, unless we're inside a block or call
+    pub fn current_env(&self) -> usize {
+        self.environment
+    }
+
+    // Number of environments in the arena, for spotting memory growth
+    pub fn env_count(&self) -> usize {
+        self.arena.len()
+    }
+
+    // Environments still reachable from the globals and the current scope. Anything else in the arena is garbage.
+    pub fn reachable_envs(&self) -> HashSet<usize> {
+        self.arena.reachable_from(&[self.globals, self.environment])
+    }
+
+    // Whether `name` can be looked up from the given environment (an id from current_env() or a closure)
+    pub fn is_defined(&self, env_id: usize, name: &str) -> bool {
+        self.arena.is_defined(env_id, name)
+    }
+
+    // Like is_defined, but only the environment itself counts, not the ones enclosing it
+    pub fn is_defined_local(&self, env_id: usize, name: &str) -> bool {
+        self.arena.is_defined_local(env_id, name)
+    }
+
+    // A variable's value as seen from the given environment, nil if there's no such variable
+    pub fn get_or_nil(&self, env_id: usize, name: &str) -> Value {
+        self.arena.get_or_nil(env_id, name)
+    }
+
+    // A global variable's value, for host code reading what a script left behind. None if it was never defined.
+    pub fn get_global(&self, name: &str) -> Option<Value> {
+        self.arena.get(self.globals, name).ok()
+    }
+
+    // Define or overwrite a global, the same as the script running "var name = value;"
+    pub fn set_global(&mut self, name: &str, value: Value) {
+        self.arena.define(self.globals, name.to_string(), value);
+    }
+
+    // Forget a global variable, returning the value it had
+    pub fn remove_global(&mut self, name: &str) -> Option<Value> {
+        self.arena.remove(self.globals, name)
+    }
+
+    // Stop runaway loops: once every loop together has gone around `limit` times, the next iteration is
+    // a runtime error. 0 means no limit. Setting it starts the count over.
+    pub fn set_max_iterations(&mut self, limit: usize) {
+        self.max_iterations = limit;
+        self.iterations = 0;
+    }
+
+    // Round non-integer numbers to this many decimals when printing, e.g. Some(2) prints 0.1 + 0.2 as 0.30
+    pub fn set_precision(&mut self, precision: Option<usize>) {
+        self.precision = precision;
+    }
+
+    // How "print" and string concatenation show a value
+    pub fn stringify(&self, value: &Value) -> String {
+        WithPrecision(value, self.precision).to_string()
+    }
+
+    // Make a Rust closure callable from Lox as a global function:
+    // interpreter.define_native("double", 1, |_, args| ...);
+    pub fn define_native(&mut self, name: &str, arity: usize,
+                         function: impl Fn(&mut Interpreter, Vec<Value>) -> Result<Value> + 'static) {
+        let host = HostFunction { name: name.to_string(), arity, function: Box::new(function) };
+        self.arena.define(self.globals, name.to_string(), Value::HostFunction(Rc::new(host)));
+    }
+
+    // The user's global variables and their values, sorted by name. Natives are left out, they're always there.
+    pub fn dump_globals(&self) -> Vec<(String, Value)> {
+        self.arena
+            .values(self.globals)
+            .into_iter()
+            .filter(|(_, value)| !matches!(value, Value::NativeFunction(_) | Value::HostFunction(_)))
+            .collect()
+    }
+
+    fn count(&mut self, kind: &'static str) {
+        if self.profiling_enabled {
+            *self.profile.entry(kind).or_insert(0) += 1;
+        }
+    }
+
+    // Run a snippet of source and give back the value of its final expression statement.
+    // Anything else as the last statement (print, var, ...) gives nil.
+    pub fn eval_str(&mut self, source: &str) -> Result<Value> {
+        let statements = Self::parse_source(source, true)?;
+
+        let Some((last, rest)) = statements.split_last() else {
+            return Ok(Value::Nil);
+        };
+        for statement in rest {
+            if !self.execute_top_level(statement)? {
+                return Ok(Value::Nil);
+            }
+        }
+        match last {
+            Stmt::Expression { expression } => self.evaluate(expression),
+            _ => {
+                self.execute_top_level(last)?;
+                Ok(Value::Nil)
+            }
+        }
+    }
+
+    // eval("...") from Lox: the same as eval_str(), in whatever scope eval() was called from.
+    // Each nesting level costs a lot of Rust stack, so nesting is limited instead of crashing on runaway recursion.
+    pub(crate) fn eval_nested(&mut self, source: &str) -> Result<Value> {
+        if self.eval_depth >= MAX_EVAL_DEPTH {
+            return Err(anyhow!("Too many nested eval() calls."));
+        }
+        self.eval_depth += 1;
+        let result = self.eval_str(source);
+        self.eval_depth -= 1;
+        result
+    }
+
+    fn parse_source(source: &str, allow_trailing_expression: bool) -> Result<Vec<Stmt>> {
+        let mut scanner = Scanner::new(source.to_string());
+        let tokens = scanner.scan_tokens()?.clone();
+
+        let mut error_reporter = ErrorReporter::new();
+        error_reporter.set_source(source);
+        let mut parser = if allow_trailing_expression {
+            Parser::new_with_trailing_expression(tokens)
+        } else {
+            Parser::new(tokens)
+        };
+        let statements = parser.parse(&mut error_reporter);
+        if error_reporter.had_error() {
+            return Err(anyhow!("Could not parse source."));
+        }
+        Ok(statements.unwrap_or_default())
+    }
+
+    // Runs another file with `target` as its global environment. For a plain import that is
+    // the importer's global environment, so everything the file defines becomes visible there.
+    fn import_file(&mut self, keyword: &Token, path: &Path, target: EnvId) -> Result<()> {
+        let canonical = fs::canonicalize(path)
+            .map_err(|err| self.runtime_error(RuntimeErrorKind::Import, keyword, &format!("Can't import '{}': {}.", path.display(), err)))?;
+
+        // The importing file is in progress too (the top-level script is never imported itself)
+        let importer = self.script_path.as_ref().and_then(|script| fs::canonicalize(script).ok());
+        let importer_added = importer.clone().is_some_and(|importer| self.importing.insert(importer));
+        let result = self.run_imported_file(keyword, path, &canonical, target);
+        if importer_added && let Some(importer) = importer {
+            self.importing.remove(&importer);
+        }
+        result
+    }
+
+    fn run_imported_file(&mut self, keyword: &Token, path: &Path, canonical: &Path, target: EnvId) -> Result<()> {
+        if self.importing.contains(canonical) {
+            return Err(self.runtime_error(RuntimeErrorKind::Import, keyword, &format!("Circular import of '{}'.", path.display())));
+        }
+        let source = fs::read_to_string(canonical)
+            .map_err(|err| self.runtime_error(RuntimeErrorKind::Import, keyword, &format!("Can't import '{}': {}.", path.display(), err)))?;
+        let statements = Self::parse_source(&source, false)
+            .map_err(|err| self.runtime_error(RuntimeErrorKind::Import, keyword, &format!("Can't import '{}': {}", path.display(), err)))?;
+
+        // Nested imports are relative to the imported file, so switch script_path while it runs
+        let previous_path = self.script_path.replace(canonical.to_path_buf());
+        let previous_env = self.environment;
+        self.importing.insert(canonical.to_path_buf());
+        self.environment = target;
+
+        let result = self.interpret(&statements);
+
+        self.environment = previous_env;
+        self.importing.remove(canonical);
+        self.script_path = previous_path;
+        result
+    }
+
     pub fn execute_block(&mut self, statements: Vec<Stmt>) -> Result<()> {
         let current_env = self.environment; // Remember current environment ID
         let block_env = self.arena.create_env_with_enclosing(current_env); // Create new block environment
@@ -83,7 +524,7 @@ impl Interpreter {
         // If any statement fails, the ? operator returns the error immediately
         let result = (|| {
             for statement in statements {
-                statement.accept(self)?;
+                self.execute(&statement)?;
             }
             Ok(())
         })();
@@ -92,25 +533,199 @@ impl Interpreter {
         result
     }
     
-    fn runtime_error(&self, token: &Token, message: &str) -> anyhow::Error {
-        RuntimeError {
-            token: token.clone(),
-            message: message.to_string(),
-        }.into()
-    }
-
-    fn check_number_operand(&self, operator: &Token, operand: &Value) -> Result<f64> {
-        match operand {
-            Value::Number(n) => Ok(*n),
-            _ => Err(self.runtime_error(operator, "Operand must be a number.")),
-        }
+    fn runtime_error(&self, kind: RuntimeErrorKind, token: &Token, message: &str) -> anyhow::Error {
+        RuntimeError::new(kind, token, message).into()
     }
 
+    // Ints are promoted to floats here, so callers get plain f64 operands
     fn check_number_operands(&self, operator: &Token, left: &Value, right: &Value) -> Result<(f64, f64)> {
         match (left, right) {
             (Value::Number(l), Value::Number(r)) => Ok((*l, *r)),
-            _ => Err(self.runtime_error(operator, "Operands must be numbers.")),
+            (Value::Int(l), Value::Number(r)) => Ok((*l as f64, *r)),
+            (Value::Number(l), Value::Int(r)) => Ok((*l, *r as f64)),
+            (Value::Int(l), Value::Int(r)) => Ok((*l as f64, *r as f64)),
+            _ => Err(self.runtime_error(RuntimeErrorKind::TypeError, operator, "Operands must be numbers.")),
+        }
+    }
+
+    // Int op Int stays an Int. Mixed int/float operands, or an i64 overflow, give a float.
+    fn arithmetic(&self, operator: &Token, left: &Value, right: &Value,
+                  int_op: fn(i64, i64) -> Option<i64>, float_op: fn(f64, f64) -> f64) -> Result<Value> {
+        if let (Value::Int(l), Value::Int(r)) = (left, right)
+            && let Some(result) = int_op(*l, *r) {
+            return Ok(Value::Int(result));
+        }
+        let (l, r) = self.check_number_operands(operator, left, right)?;
+        Ok(Value::Number(float_op(l, r)))
+    }
+
+    // Count one more loop iteration against the limit from set_max_iterations()
+    fn loop_iteration(&mut self, keyword: &Token) -> Result<()> {
+        self.iterations += 1;
+        if self.max_iterations > 0 && self.iterations > self.max_iterations {
+            return Err(self.runtime_error(RuntimeErrorKind::IterationLimit, keyword, "Loop iteration limit exceeded."));
+        }
+        Ok(())
+    }
+
+    // Runs a while loop and gives back its value: the value of "break value;",
+    // or nil if the loop ended because the condition became false
+    fn execute_loop(&mut self, keyword: &Token, condition: &Expr, body: &Stmt) -> Result<Value> {
+        while self.evaluate(condition)?.is_truthy() {
+            self.loop_iteration(keyword)?;
+            match self.execute(body) {
+                Err(err) if err.is::<BreakSignal>() => return Ok(self.unwinding_value.take().unwrap_or(Value::Nil)),
+                Err(err) if !err.is::<ContinueSignal>() => return Err(err),
+                _ => {}
+            }
+        }
+        Ok(Value::Nil)
+    }
+
+    // The loop itself, run with self.environment already set to the first iteration's environment
+    fn execute_for(&mut self, keyword: &Token, initializer: Option<&Stmt>, condition: Option<&Expr>,
+                   increment: Option<&Expr>, body: &Stmt) -> Result<()> {
+        if let Some(initializer) = initializer {
+            self.execute(initializer)?;
+        }
+        loop {
+            if let Some(condition) = condition
+                && !self.evaluate(condition)?.is_truthy() {
+                return Ok(());
+            }
+            self.loop_iteration(keyword)?;
+            match self.execute(body) {
+                Err(err) if err.is::<BreakSignal>() => {
+                    self.unwinding_value = None; // a for loop has no value, so "break value;" just stops
+                    return Ok(());
+                }
+                // "continue" still falls through to the increment, or the loop would never end
+                Err(err) if !err.is::<ContinueSignal>() => return Err(err),
+                _ => {}
+            }
+
+            self.environment = self.arena.duplicate(self.environment);
+            if let Some(increment) = increment {
+                self.evaluate(increment)?;
+            }
+        }
+    }
+
+    // List indexes must be whole numbers inside the list
+    fn list_index(&self, bracket: &Token, index: &Value, len: usize) -> Result<usize> {
+        let i = match index {
+            Value::Int(i) => *i,
+            Value::Number(n) if n.fract() == 0.0 => *n as i64,
+            _ => return Err(self.runtime_error(RuntimeErrorKind::TypeError, bracket, "List index must be an integer.")),
+        };
+        if i < 0 || i as usize >= len {
+            return Err(self.runtime_error(RuntimeErrorKind::IndexOutOfRange, bracket, &format!("List index {} is out of range for length {}.", i, len)));
+        }
+        Ok(i as usize)
+    }
+
+    // Like list_index, counting characters, but negative indices count from the end: "hello"[-1] is "o"
+    fn string_index(&self, bracket: &Token, index: &Value, len: usize) -> Result<usize> {
+        let i = match index {
+            Value::Int(i) => *i,
+            Value::Number(n) if n.fract() == 0.0 => *n as i64,
+            _ => return Err(self.runtime_error(RuntimeErrorKind::TypeError, bracket, "String index must be an integer.")),
+        };
+        let from_start = if i < 0 { i + len as i64 } else { i };
+        if from_start < 0 || from_start as usize >= len {
+            return Err(self.runtime_error(RuntimeErrorKind::IndexOutOfRange, bracket, &format!("String index {} is out of range for length {}.", i, len)));
         }
+        Ok(from_start as usize)
+    }
+
+    // The values a for-in loop walks through. Lists and maps are copied first, so the body may change them safely.
+    fn iterate(&self, token: &Token, iterable: &Value) -> Result<Box<dyn Iterator<Item = Value>>> {
+        Ok(match iterable {
+            Value::Range { start, end, inclusive: false } => Box::new((*start..*end).map(Value::Int)),
+            Value::Range { start, end, inclusive: true } => Box::new((*start..=*end).map(Value::Int)),
+            Value::List(elements) => Box::new(elements.borrow().clone().into_iter()),
+            Value::Map(entries) => {
+                let keys: Vec<Value> = entries.borrow().keys().map(|key| Value::String(key.clone())).collect();
+                Box::new(keys.into_iter())
+            }
+            Value::String(s) => {
+                let chars: Vec<Value> = s.chars().map(|c| Value::String(c.to_string())).collect();
+                Box::new(chars.into_iter())
+            }
+            _ => return Err(self.runtime_error(RuntimeErrorKind::TypeError, token, "Can only iterate over ranges, lists, maps and strings.")),
+        })
+    }
+
+    fn map_key(&self, token: &Token, key: Value) -> Result<String> {
+        match key {
+            Value::String(s) => Ok(s),
+            _ => Err(self.runtime_error(RuntimeErrorKind::TypeError, token, "Map keys must be strings.")),
+        }
+    }
+
+    // Call anything callable with arguments that are already evaluated. Natives like map() use this to call back
+    // into Lox. Errors without a token of their own (wrong argument count, errors from natives) are plain
+    // errors here, and visit_call_expr places them at the call with at_call_site().
+    pub fn call_value(&mut self, callee: Value, mut arguments: Vec<Value>) -> Result<Value> {
+        let expected = match &callee {
+            Value::Function(function) => Arity::Fixed(function.arity()),
+            Value::NativeFunction(function) => function.arity(), // Variadic natives take whatever they're given
+            Value::HostFunction(host) => Arity::Fixed(host.arity),
+            // The receiver is the native's first argument, so it doesn't count here
+            Value::BoundNative(bound) => match bound.function.arity() {
+                Arity::Fixed(with_receiver) => Arity::Fixed(with_receiver - 1),
+                Arity::Variadic => Arity::Variadic,
+            },
+            _ => return Err(CallError {
+                kind: RuntimeErrorKind::TypeError,
+                message: "Can only call functions and classes.".to_string(),
+            }.into()),
+        };
+        if let Arity::Fixed(arity) = expected
+            && arguments.len() != arity {
+            return Err(CallError {
+                kind: RuntimeErrorKind::Arity,
+                message: format!("Expected {} arguments but got {}.", arity, arguments.len()),
+            }.into());
+        }
+
+        match callee {
+            Value::Function(function) => self.call_lox_function(&function, arguments),
+            Value::NativeFunction(function) => function.call(self, arguments),
+            Value::HostFunction(host) => (host.function)(self, arguments),
+            Value::BoundNative(bound) => {
+                arguments.insert(0, bound.receiver.clone());
+                bound.function.call(self, arguments)
+            }
+            _ => unreachable!("checked above"),
+        }
+    }
+
+    // Shared by "." and "?."
+    fn get_property(&mut self, object: Value, name: &Token) -> Result<Value> {
+        match object {
+            Value::Module(module) => self.arena.get(module.env, &name.lexeme).map_err(|_| {
+                self.runtime_error(RuntimeErrorKind::UndefinedProperty, name, &format!("Undefined property '{}' in module '{}'.", name.lexeme, module.name))
+            }),
+            Value::String(s) => match NativeFunction::string_method(&name.lexeme) {
+                Some(function) => Ok(Value::BoundNative(Rc::new(BoundNative { receiver: Value::String(s), function }))),
+                None => Err(self.runtime_error(RuntimeErrorKind::UndefinedProperty, name, &format!("Undefined method '{}' on string.", name.lexeme))),
+            },
+            _ => Err(self.runtime_error(RuntimeErrorKind::TypeError, name, "Only modules and strings have properties.")),
+        }
+    }
+
+    // Natives don't know the call site, so attach the paren token to their errors
+    fn at_call_site(&self, paren: &Token, result: Result<Value>) -> Result<Value> {
+        result.map_err(|err| {
+            if err.is::<RuntimeError>() {
+                err
+            } else if let Some(call_error) = err.downcast_ref::<CallError>() {
+                self.runtime_error(call_error.kind, paren, &call_error.message)
+            } else {
+                self.runtime_error(RuntimeErrorKind::Native, paren, &err.to_string())
+            }
+        })
     }
 
     pub fn call_lox_function(&mut self, function: &LoxFunction, arguments: Vec<Value>) -> Result<Value> {
@@ -124,32 +739,32 @@ impl Interpreter {
 
         let current_env = self.environment; // Remember current environment
         
-        // Create new environment with function's closure as parent
+        // Create new environment with function's closure as parent.
+        // Recursion needs nothing extra: visit_function_stmt defined the function in that closure.
         let call_env = self.arena.create_env_with_enclosing(function.closure());
         
-        // Add function to its own environment for recursion
-        self.arena.define(call_env, function.name().to_string(), Value::Function(function.clone()));
-        
         // Bind parameters to arguments
         for (param, arg) in function.declaration().params.iter().zip(arguments.iter()) {
             self.arena.define(call_env, param.lexeme.clone(), arg.clone());
         }
         
         self.environment = call_env; // Switch to function's environment
+        self.call_depth += 1;
 
         let result: anyhow::Result<Value> = (|| {
             for statement in &function.declaration().body {
-                statement.accept(self)?;
+                self.execute(statement)?;
             }
             Ok(Value::Nil)
         })();
 
+        self.call_depth -= 1;
         self.environment = current_env; // Restore previous environment
 
         match result {
             Err(err) => {
-                if let Some(return_val) = err.downcast_ref::<ReturnValue>() {
-                    Ok(return_val.value.clone())
+                if err.is::<ReturnValue>() {
+                    Ok(self.unwinding_value.take().unwrap_or(Value::Nil))
                 } else {
                     Err(err)
                 }
@@ -161,24 +776,37 @@ impl Interpreter {
 
 impl StmtVisitor<Result<()>> for Interpreter {
     fn visit_expression_stmt(&mut self, _stmt: &Stmt, expression: &Expr) -> Result<()> {
-        // TODO: Evaluate expression and discard result
-        expression.accept(self)?;
+        self.count("expression_stmt");
+        // The result is thrown away, so a pure expression has nothing left to do. See Expr::is_pure().
+        if expression.is_pure() {
+            return Ok(());
+        }
+        self.evaluate(expression)?;
         Ok(())
     }
 
-    fn visit_print_stmt(&mut self, _stmt: &Stmt, expression: &Expr) -> Result<()> {
+    fn visit_print_stmt(&mut self, _stmt: &Stmt, expressions: &[Expr]) -> Result<()> {
+        self.count("print_stmt");
         // TODO: Evaluate expression and print result
-        let value = expression.accept(self)?;
-        println!("{}", value);
+        let mut values = Vec::new();
+        for expression in expressions {
+            let value = self.evaluate(expression)?;
+            values.push(self.stringify(&value));
+        }
+        // Flushed every time, so output redirected to a file still lands before any error on stderr
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{}", values.join(" "))?;
+        stdout.flush()?;
         Ok(())
     }
 
     fn visit_var_stmt(&mut self, _stmt: &Stmt, name: &Token, initializer: &Option<Box<Expr>>) -> Result<()> {
+        self.count("var_stmt");
         // TODO: 
         // If initializer exists, evaluate it, otherwise use nil
         // Define variable in environment
         let value = if let Some(init) = initializer {
-            init.accept(self)?
+            self.evaluate(init)?
         } else {
             Value::Nil
         };
@@ -187,7 +815,61 @@ impl StmtVisitor<Result<()>> for Interpreter {
         Ok(())
     }
 
+    fn visit_destructure_stmt(&mut self, _stmt: &Stmt, bracket: &Token, names: &[Token],
+                              rest: &Option<Token>, initializer: &Expr) -> Result<()> {
+        self.count("destructure_stmt");
+        let Value::List(elements) = self.evaluate(initializer)? else {
+            return Err(self.runtime_error(RuntimeErrorKind::TypeError, bracket, "Can only destructure a list."));
+        };
+        let elements = elements.borrow().clone();
+
+        if rest.is_none() && elements.len() != names.len() {
+            return Err(self.runtime_error(RuntimeErrorKind::Destructure, bracket,
+                &format!("Expected {} values to destructure but got {}.", names.len(), elements.len())));
+        }
+        if elements.len() < names.len() {
+            return Err(self.runtime_error(RuntimeErrorKind::Destructure, bracket,
+                &format!("Expected at least {} values to destructure but got {}.", names.len(), elements.len())));
+        }
+
+        let mut elements = elements.into_iter();
+        for name in names {
+            let value = elements.next().unwrap_or(Value::Nil);
+            self.arena.define(self.environment, name.lexeme.clone(), value);
+        }
+        if let Some(rest) = rest {
+            self.arena.define(self.environment, rest.lexeme.clone(), Value::list(elements.collect()));
+        }
+        Ok(())
+    }
+
+    // Only runtime errors are caught. return and break are errors in Rust terms too, but they keep unwinding.
+    fn visit_try_stmt(&mut self, _stmt: &Stmt, body: &[Stmt], name: &Token, handler: &[Stmt]) -> Result<()> {
+        self.count("try_stmt");
+        let err = match self.execute_block(body.to_vec()) {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+        let runtime_error = err.downcast::<RuntimeError>()?;
+
+        let previous = self.environment;
+        let catch_env = self.arena.create_env_with_enclosing(previous);
+        self.arena.define(catch_env, name.lexeme.clone(), Value::String(runtime_error.message));
+        self.environment = catch_env;
+        let result = self.execute_block(handler.to_vec());
+        self.environment = previous;
+        result
+    }
+
+    // The thrown value becomes the error message, so throw 42; is caught as "42"
+    fn visit_throw_stmt(&mut self, _stmt: &Stmt, keyword: &Token, value: &Expr) -> Result<()> {
+        self.count("throw_stmt");
+        let value = self.evaluate(value)?;
+        Err(RuntimeError::from_throw(keyword, self.stringify(&value)).into())
+    }
+
     fn visit_block_stmt(&mut self, _stmt: &Stmt, statements: Vec<Stmt>) -> Result<()> {
+        self.count("block_stmt");
         self.execute_block(statements)
     }
 
@@ -195,35 +877,76 @@ impl StmtVisitor<Result<()>> for Interpreter {
                                 condition: &Expr, 
                                 then_branch: &Stmt, 
                                 else_branch: &Option<Box<Stmt>>) -> Result<()> {
+        self.count("if_stmt");
         // TODO: 
         // 1. Evaluate the condition
         // 2. Check if it's truthy using Value::is_truthy()
         // 3. Execute then_branch if true, else_branch if false and it exists
-        let condition = condition.accept(self)?; 
+        let condition = self.evaluate(condition)?; 
         // This "self" implements both ExprVisitor and StmtVisitor, so it can automatically
         // coerce itself to the right trait obj type to "condition"
         if condition.is_truthy() {
-            then_branch.accept(self)?;
+            self.execute(then_branch)?;
         }
         else if let Some(else_stmt) = else_branch {
-            else_stmt.accept(self)?;
+            self.execute(else_stmt)?;
         }
         
         Ok(())
     }
 
-    fn visit_while_stmt(&mut self, _stmt: &Stmt, condition: &Expr, body: &Stmt) -> Result<()> {
+    fn visit_for_in_stmt(&mut self, _stmt: &Stmt, name: &Token, iterable: &Expr, body: &Stmt) -> Result<()> {
+        self.count("for_in_stmt");
+        let iterable = self.evaluate(iterable)?;
+        let outer = self.environment;
+        for value in self.iterate(name, &iterable)? {
+            self.loop_iteration(name)?;
+            // A fresh environment each time around, so a closure made in the body keeps its own value
+            let loop_env = self.arena.create_env_with_enclosing(outer);
+            self.arena.define(loop_env, name.lexeme.clone(), value);
+            self.environment = loop_env;
+            let result = self.execute(body);
+            self.environment = outer;
+
+            match result {
+                Err(err) if err.is::<BreakSignal>() => {
+                    self.unwinding_value = None; // for-in has no value, so "break value;" just stops
+                    break;
+                }
+                Err(err) if !err.is::<ContinueSignal>() => return Err(err),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_while_stmt(&mut self, _stmt: &Stmt, keyword: &Token, condition: &Expr, body: &Stmt) -> Result<()> {
+        self.count("while_stmt");
         // TODO:
         // 1. Loop while condition is truthy
         // 2. Execute body in each iteration
         // Be careful with Rust's ownership - you might need to use references
-        while condition.accept(self)?.is_truthy() {
-            body.accept(self)?;
-        }
+        self.execute_loop(keyword, condition, body)?;
         Ok(())
     }
 
+    /*
+    Each iteration runs in its own environment holding a copy of the loop variables. After the body,
+    the current values are copied into the next iteration's environment and the increment runs there.
+    So a closure made in the body keeps the i of its own iteration, like "let" in a JavaScript for loop.
+    */
+    fn visit_for_stmt(&mut self, _stmt: &Stmt, keyword: &Token, initializer: Option<&Stmt>, condition: Option<&Expr>,
+                      increment: Option<&Expr>, body: &Stmt) -> Result<()> {
+        self.count("for_stmt");
+        let outer = self.environment;
+        self.environment = self.arena.create_env_with_enclosing(outer);
+        let result = self.execute_for(keyword, initializer, condition, increment, body);
+        self.environment = outer;
+        result
+    }
+
     fn visit_function_stmt(&mut self, _stmt: &Stmt, name: &Token, params: &[Token], body: &[Stmt]) -> Result<()> {
+        self.count("function_stmt");
         // TODO: Create function object and store in environment
         // 1. Create FunctionDeclaration
         // 2. Capture current environment as closure
@@ -243,58 +966,116 @@ impl StmtVisitor<Result<()>> for Interpreter {
         Ok(())
     }
     
-    fn visit_return_stmt(&mut self, _stmt: &Stmt, _keyword: &Token, value: &Option<Box<Expr>>) -> Result<()> {
+    fn visit_return_stmt(&mut self, _stmt: &Stmt, keyword: &Token, value: &Option<Box<Expr>>) -> Result<()> {
+        self.count("return_stmt");
+        if self.call_depth == 0 && !self.repl_mode {
+            return Err(self.runtime_error(RuntimeErrorKind::Other, keyword, "Can't return from top-level code."));
+        }
         // TODO: Evaluate return value and "throw" it as a special error
         // 1. Evaluate value (or use nil if None)
         // 2. Create ReturnValue error
         // 3. Return the error (this will unwind the stack)
         let val = if let Some(v) = value {
-            v.accept(self)?
+            self.evaluate(v)?
         } else {
             Value::Nil
         };
         
         // Not an actual error. We only need to bypass the remaining statements
         // "?" after "accept(self)" immediately exits the loop
-        Err(ReturnValue { value: val }.into())
+        self.unwinding_value = Some(val);
+        Err(ReturnValue.into())
+    }
+
+    fn visit_break_stmt(&mut self, _stmt: &Stmt, _keyword: &Token, value: &Option<Box<Expr>>) -> Result<()> {
+        self.count("break_stmt");
+        let val = if let Some(v) = value {
+            self.evaluate(v)?
+        } else {
+            Value::Nil
+        };
+        self.unwinding_value = Some(val);
+        Err(BreakSignal.into())
+    }
+
+    fn visit_continue_stmt(&mut self, _stmt: &Stmt, _keyword: &Token) -> Result<()> {
+        self.count("continue_stmt");
+        Err(ContinueSignal.into())
+    }
+
+    fn visit_empty_stmt(&mut self, _stmt: &Stmt) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_import_stmt(&mut self, _stmt: &Stmt, keyword: &Token, path: &Token, alias: &Option<Token>) -> Result<()> {
+        self.count("import_stmt");
+        let Some(LiteralValue::String(file)) = &path.literal else {
+            return Err(self.runtime_error(RuntimeErrorKind::Import, path, "Import path must be a string."));
+        };
+        let base = self.script_path.as_ref().and_then(|script| script.parent()).unwrap_or(Path::new(""));
+        let full_path = base.join(file);
+
+        match alias {
+            // A module gets a fresh global environment, so its names can't clash with ours
+            Some(alias) => {
+                let module_env = self.arena.create_env();
+                self.define_natives(module_env);
+                self.import_file(keyword, &full_path, module_env)?;
+                let module = LoxModule { name: alias.lexeme.clone(), env: module_env };
+                self.arena.define(self.environment, alias.lexeme.clone(), Value::Module(Rc::new(module)));
+                Ok(())
+            }
+            None => {
+                let globals = self.arena.root(self.environment);
+                self.import_file(keyword, &full_path, globals)
+            }
+        }
     }
 }
 
 impl ExprVisitor<Result<Value>> for Interpreter {
     fn visit_literal_expr(&mut self, _expr: &Expr, value: &Option<LiteralValue>) -> Result<Value> {
+        self.count("literal_expr");
         // TODO: Convert LiteralValue to Value
         // This should be straightforward mapping
         match value {
             Some(LiteralValue::Boolean(b)) => Ok(Value::Boolean(*b)),
             Some(LiteralValue::Nil) | None => Ok(Value::Nil),
             Some(LiteralValue::Number(n)) => Ok(Value::Number(*n)),
+            Some(LiteralValue::Int(n)) => Ok(Value::Int(*n)),
             Some(LiteralValue::String(s)) => Ok(Value::String(s.clone())),
         }
     }
 
     fn visit_grouping_expr(&mut self, _expr: &Expr, expression: &Expr) -> Result<Value> {
+        self.count("grouping_expr");
         // TODO: Just evaluate the inner expression
-        expression.accept(self)
+        self.evaluate(expression)
     }
 
     fn visit_unary_expr(&mut self, _expr: &Expr, operator: &Token, right: &Expr) -> Result<Value> {
+        self.count("unary_expr");
         // TODO: Evaluate the right operand first, then apply the operator
         // Handle TokenType::Bang and TokenType::Minus
         // Remember to check types and throw runtime errors for invalid operations
-        let mut right_value = right.accept(self)?;
+        let right_value = self.evaluate(right)?;
         match operator.token_type {
             TokenType::Bang => {
                 Ok(Value::Boolean(!right_value.is_truthy()))
             },
             TokenType::Minus => {
-                let num = self.check_number_operand(operator, &right_value)?;
-                Ok(Value::Number(-num))
+                match right_value {
+                    Value::Int(n) => Ok(n.checked_neg().map_or(Value::Number(-(n as f64)), Value::Int)),
+                    Value::Number(n) => Ok(Value::Number(-n)),
+                    _ => Err(self.runtime_error(RuntimeErrorKind::TypeError, operator, "Operand must be a number.")),
+                }
             },
             _ => Err(anyhow!("Unknown unary operator: {:?}", operator.token_type)),
         }
     }
 
     fn visit_binary_expr(&mut self, _expr: &Expr, left: &Expr, operator: &Token, right: &Expr) -> Result<Value> {
+        self.count("binary_expr");
         // TODO: This is the big one! Handle all binary operators:
         // - Arithmetic: +, -, *, /
         // - Comparison: >, >=, <, <=
@@ -304,34 +1085,40 @@ impl ExprVisitor<Result<Value>> for Interpreter {
         // - Division by zero
         // - String concatenation with +
         // - Type checking for arithmetic operations
-        let left_value = left.accept(self)?;
-        let right_value = right.accept(self)?;
+        let left_value = self.evaluate(left)?;
+        let right_value = self.evaluate(right)?;
 
         match operator.token_type {
             // Arithmetic operators
             TokenType::Plus => {
                 // Special case: + can be arithmetic OR string concatenation
                 match (&left_value, &right_value) {
-                    (Value::Number(l), Value::Number(r)) => Ok(Value::Number(l + r)),
+                    (Value::Number(_) | Value::Int(_), Value::Number(_) | Value::Int(_)) => {
+                        self.arithmetic(operator, &left_value, &right_value, i64::checked_add, |l, r| l + r)
+                    }
                     (Value::String(l), Value::String(r)) => Ok(Value::String(format!("{}{}", l, r))),
                     // In Lox, if either operand is a string, both are converted to strings
-                    (Value::String(l), r) => Ok(Value::String(format!("{}{}", l, r))),
-                    (l, Value::String(r)) => Ok(Value::String(format!("{}{}", l, r))),
-                    _ => Err(self.runtime_error(operator, "Operands must be two numbers or two strings.")),
+                    (Value::String(l), r) => Ok(Value::String(format!("{}{}", l, self.stringify(r)))),
+                    (l, Value::String(r)) => Ok(Value::String(format!("{}{}", self.stringify(l), r))),
+                    _ => Err(self.runtime_error(RuntimeErrorKind::TypeError, operator, "Operands must be two numbers or two strings.")),
                 }
             }
             TokenType::Minus => {
-                let (l, r) = self.check_number_operands(operator, &left_value, &right_value)?;
-                Ok(Value::Number(l - r))
+                self.arithmetic(operator, &left_value, &right_value, i64::checked_sub, |l, r| l - r)
             }
             TokenType::Star => {
-                let (l, r) = self.check_number_operands(operator, &left_value, &right_value)?;
-                Ok(Value::Number(l * r))
+                self.arithmetic(operator, &left_value, &right_value, i64::checked_mul, |l, r| l * r)
+            }
+            // An Int to a non-negative Int power stays an Int, 2 ** -1 gives 0.5
+            TokenType::StarStar => {
+                self.arithmetic(operator, &left_value, &right_value,
+                                |l, r| u32::try_from(r).ok().and_then(|r| l.checked_pow(r)), f64::powf)
             }
+            // Division always gives a float, so 5 / 2 is 2.5
             TokenType::Slash => {
                 let (l, r) = self.check_number_operands(operator, &left_value, &right_value)?;
                 if r == 0.0 {
-                    return Err(self.runtime_error(operator, "Division by zero."));
+                    return Err(RuntimeError::division_by_zero(operator).into());
                 }
                 Ok(Value::Number(l / r))
             }
@@ -367,25 +1154,28 @@ impl ExprVisitor<Result<Value>> for Interpreter {
     }
 
     fn visit_variable_expr(&mut self, _expr: &Expr, name: &Token) -> Result<Value> {
+        self.count("variable_expr");
         // TODO: Look up variable in environment
         // Convert environment errors to runtime errors
         self.arena.get(self.environment, &name.lexeme)
-            .map_err(|_| self.runtime_error(name, &format!("Undefined variable '{}'.", name.lexeme)))
+            .map_err(|_| RuntimeError::undefined_variable(name).into())
     }
 
     fn visit_assign_expr(&mut self, _expr: &Expr, name: &Token, value: &Expr) -> Result<Value> {
-        let val = value.accept(self)?;
+        self.count("assign_expr");
+        let val = self.evaluate(value)?;
         self.arena.assign(self.environment, &name.lexeme, val.clone())
-            .map_err(|_| self.runtime_error(name, &format!("Undefined variable '{}'.", name.lexeme)))?;
+            .map_err(|_| RuntimeError::undefined_variable(name))?;
         Ok(val)
     }
 
     fn visit_logical_expr(&mut self, _expr: &Expr, left: &Expr, operator: &Token, right: &Expr) -> Result<Value> {
+        self.count("logical_expr");
         // TODO: Implement short-circuiting logic
         // For "or": if left is truthy, return left, otherwise return right
         // For "and": if left is falsy, return left, otherwise return right
         
-        let left_value = left.accept(self)?;
+        let left_value = self.evaluate(left)?;
         
         match operator.token_type {
             TokenType::Or => {
@@ -394,7 +1184,7 @@ impl ExprVisitor<Result<Value>> for Interpreter {
                     Ok(left_value)
                 } else {
                     // TODO: Evaluate and return right
-                    right.accept(self)
+                    self.evaluate(right)
                 }
             }
             TokenType::And => {
@@ -403,7 +1193,7 @@ impl ExprVisitor<Result<Value>> for Interpreter {
                     Ok(left_value)
                 } else {
                     // TODO: Evaluate and return right
-                    right.accept(self)
+                    self.evaluate(right)
                 }
             }
             _ => Err(anyhow!("Unknown logical operator: {:?}", operator.token_type)),
@@ -411,6 +1201,7 @@ impl ExprVisitor<Result<Value>> for Interpreter {
     }
 
     fn visit_call_expr(&mut self, _expr: &Expr, callee: &Expr, paren: &Token, arguments: &[Expr]) -> Result<Value> {
+        self.count("call_expr");
         // TODO: This is the big one! Function calls
         // 1. Evaluate callee (should be a function)
         // 2. Evaluate all arguments
@@ -420,28 +1211,140 @@ impl ExprVisitor<Result<Value>> for Interpreter {
         // Simple case: add is callee_value
         // More complex case: add(1,2) is callee_value
         // Error case: f(1,2) when f is defined by var f = 'a';
-        let callee_value = callee.accept(self)?;
+        let callee_value = self.evaluate(callee)?;
         let mut args = Vec::new();
         for argument in arguments {
-            args.push(argument.accept(self)?);
+            args.push(self.evaluate(argument)?);
         }
+        let result = self.call_value(callee_value, args);
+        self.at_call_site(paren, result)
+    }
 
-        match callee_value {
-            Value::Function(function) => {
-                if arguments.len() != function.arity() {
-                    return Err(self.runtime_error(paren, 
-                        &format!("Expected {} arguments but got {}.", function.arity(), arguments.len())));
-                }
-                self.call_lox_function(&function, args)
+    fn visit_loop_expr(&mut self, _expr: &Expr, keyword: &Token, condition: &Expr, body: &Stmt) -> Result<Value> {
+        self.count("loop_expr");
+        self.execute_loop(keyword, condition, body)
+    }
+
+    fn visit_list_expr(&mut self, _expr: &Expr, elements: &[Expr]) -> Result<Value> {
+        self.count("list_expr");
+        let mut values = Vec::new();
+        for element in elements {
+            values.push(self.evaluate(element)?);
+        }
+        Ok(Value::list(values))
+    }
+
+    fn visit_map_expr(&mut self, _expr: &Expr, brace: &Token, entries: &[(Expr, Expr)]) -> Result<Value> {
+        self.count("map_expr");
+        let mut map = BTreeMap::new();
+        for (key, value) in entries {
+            let key = self.evaluate(key)?;
+            let key = self.map_key(brace, key)?;
+            map.insert(key, self.evaluate(value)?);
+        }
+        Ok(Value::map(map))
+    }
+
+    fn visit_index_expr(&mut self, _expr: &Expr, object: &Expr, bracket: &Token, index: &Expr) -> Result<Value> {
+        self.count("index_expr");
+        let object = self.evaluate(object)?;
+        let index = self.evaluate(index)?;
+        match object {
+            Value::List(elements) => {
+                let elements = elements.borrow();
+                let i = self.list_index(bracket, &index, elements.len())?;
+                Ok(elements[i].clone())
             }
-            Value::NativeFunction(function) => {
-                if arguments.len() != function.arity() {
-                    return Err(self.runtime_error(paren, 
-                        &format!("Expected {} arguments but got {}.", function.arity(), arguments.len())));
-                }
-                function.call(self, args)
+            Value::Map(entries) => {
+                let key = self.map_key(bracket, index)?;
+                entries.borrow().get(&key).cloned()
+                    .ok_or_else(|| self.runtime_error(RuntimeErrorKind::UndefinedProperty, bracket, &format!("Undefined key '{}'.", key)))
+            }
+            // A one-character string, there's no char type. Strings are read-only, so there's no s[i] = c.
+            Value::String(s) => {
+                let chars: Vec<char> = s.chars().collect();
+                let i = self.string_index(bracket, &index, chars.len())?;
+                Ok(Value::String(chars[i].to_string()))
+            }
+            _ => Err(self.runtime_error(RuntimeErrorKind::TypeError, bracket, "Only lists, maps and strings can be indexed.")),
+        }
+    }
+
+    fn visit_index_set_expr(&mut self, _expr: &Expr, object: &Expr, bracket: &Token, index: &Expr, value: &Expr) -> Result<Value> {
+        self.count("index_set_expr");
+        // For a[0][1] = 5, object is a[0]: evaluating it gives the inner list, which shares storage with a
+        let object = self.evaluate(object)?;
+        let index = self.evaluate(index)?;
+        let value = self.evaluate(value)?;
+        match object {
+            Value::List(elements) => {
+                let mut elements = elements.borrow_mut();
+                let i = self.list_index(bracket, &index, elements.len())?;
+                elements[i] = value.clone();
             }
-            _ => Err(self.runtime_error(paren, "Can only call functions and classes."))
+            Value::Map(entries) => {
+                let key = self.map_key(bracket, index)?;
+                entries.borrow_mut().insert(key, value.clone());
+            }
+            _ => return Err(self.runtime_error(RuntimeErrorKind::TypeError, bracket, "Only lists and maps can be indexed.")),
+        }
+        Ok(value)
+    }
+
+    fn visit_range_expr(&mut self, _expr: &Expr, start: &Expr, operator: &Token, end: &Expr) -> Result<Value> {
+        self.count("range_expr");
+        let start = self.evaluate(start)?;
+        let end = self.evaluate(end)?;
+        match (start, end) {
+            (Value::Int(start), Value::Int(end)) => Ok(Value::Range {
+                start,
+                end,
+                inclusive: operator.token_type == TokenType::DotDotEqual,
+            }),
+            _ => Err(self.runtime_error(RuntimeErrorKind::TypeError, operator, "Range bounds must be integers.")),
+        }
+    }
+
+    fn visit_get_expr(&mut self, _expr: &Expr, object: &Expr, name: &Token) -> Result<Value> {
+        self.count("get_expr");
+        let object = self.evaluate(object)?;
+        self.get_property(object, name)
+    }
+
+    // Only a module's variables can be set this way, and only ones it already has: m.count = 2
+    fn visit_set_expr(&mut self, _expr: &Expr, object: &Expr, name: &Token, value: &Expr) -> Result<Value> {
+        self.count("set_expr");
+        let object = self.evaluate(object)?;
+        let value = self.evaluate(value)?;
+        match object {
+            Value::Module(module) => {
+                self.arena.assign(module.env, &name.lexeme, value.clone()).map_err(|_| {
+                    self.runtime_error(RuntimeErrorKind::UndefinedProperty, name,
+                        &format!("Undefined property '{}' in module '{}'.", name.lexeme, module.name))
+                })?;
+                Ok(value)
+            }
+            _ => Err(self.runtime_error(RuntimeErrorKind::TypeError, name, "Only modules have properties that can be set.")),
         }
     }
-}
\ No newline at end of file
+
+    // The value is evaluated outside the new scope, so let x = x + 1 in ... reads the outer x
+    fn visit_let_expr(&mut self, _expr: &Expr, name: &Token, value: &Expr, body: &Expr) -> Result<Value> {
+        self.count("let_expr");
+        let value = self.evaluate(value)?;
+        let current_env = self.environment;
+        self.environment = self.arena.create_env_with_enclosing(current_env);
+        self.arena.define(self.environment, name.lexeme.clone(), value);
+        let result = self.evaluate(body);
+        self.environment = current_env;
+        result
+    }
+
+    fn visit_optional_get_expr(&mut self, _expr: &Expr, object: &Expr, name: &Token) -> Result<Value> {
+        self.count("optional_get_expr");
+        match self.evaluate(object)? {
+            Value::Nil => Ok(Value::Nil),
+            object => self.get_property(object, name),
+        }
+    }
+}
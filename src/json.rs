@@ -0,0 +1,262 @@
+/*
+The AST as JSON, for tools built on top of the parser (lox --emit-json file.lox).
+
+Every node is an object with a "type" tag and one field per child, named like the Rust fields:
+  var x = 1 + 2;
+becomes
+  {"type":"Var","name":{"lexeme":"x","line":1,"column":5},"initializer":{"type":"Binary","left":...}}
+
+Tokens are {"lexeme", "line", "column"}, literal values are plain JSON (nil is null), and a missing
+optional child (no initializer, no else branch, ...) is null.
+*/
+
+use crate::expr::Expr;
+use crate::stmt::Stmt;
+use crate::token::{LiteralValue, Token};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>), // Keeps the field order, so the output is stable
+}
+
+pub fn program_to_json(statements: &[Stmt]) -> Json {
+    Json::Array(statements.iter().map(stmt_to_json).collect())
+}
+
+pub fn stmt_to_json(stmt: &Stmt) -> Json {
+    match stmt {
+        Stmt::Expression { expression } => node("Expression", vec![("expression", expr_to_json(expression))]),
+        Stmt::Print { expressions } => node("Print", vec![("expressions", exprs(expressions))]),
+        Stmt::Var { name, initializer } => node("Var", vec![
+            ("name", token(name)),
+            ("initializer", optional(initializer.as_deref().map(expr_to_json))),
+        ]),
+        Stmt::Block { statements } => node("Block", vec![("statements", program_to_json(statements))]),
+        Stmt::If { condition, then_branch, else_branch } => node("If", vec![
+            ("condition", expr_to_json(condition)),
+            ("then_branch", stmt_to_json(then_branch)),
+            ("else_branch", optional(else_branch.as_deref().map(stmt_to_json))),
+        ]),
+        Stmt::While { keyword, condition, body } => node("While", vec![
+            ("keyword", token(keyword)),
+            ("condition", expr_to_json(condition)),
+            ("body", stmt_to_json(body)),
+        ]),
+        Stmt::For { keyword, initializer, condition, increment, body } => node("For", vec![
+            ("keyword", token(keyword)),
+            ("initializer", optional(initializer.as_deref().map(stmt_to_json))),
+            ("condition", optional(condition.as_deref().map(expr_to_json))),
+            ("increment", optional(increment.as_deref().map(expr_to_json))),
+            ("body", stmt_to_json(body)),
+        ]),
+        Stmt::Function { name, params, body } => node("Function", vec![
+            ("name", token(name)),
+            ("params", tokens(params)),
+            ("body", program_to_json(body)),
+        ]),
+        Stmt::Return { keyword, value } => node("Return", vec![
+            ("keyword", token(keyword)),
+            ("value", optional(value.as_deref().map(expr_to_json))),
+        ]),
+        Stmt::Break { keyword, value } => node("Break", vec![
+            ("keyword", token(keyword)),
+            ("value", optional(value.as_deref().map(expr_to_json))),
+        ]),
+        Stmt::Continue { keyword } => node("Continue", vec![("keyword", token(keyword))]),
+        Stmt::Empty => node("Empty", vec![]),
+        Stmt::Import { keyword, path, alias } => node("Import", vec![
+            ("keyword", token(keyword)),
+            ("path", token(path)),
+            ("alias", optional(alias.as_ref().map(token))),
+        ]),
+        Stmt::ForIn { name, iterable, body } => node("ForIn", vec![
+            ("name", token(name)),
+            ("iterable", expr_to_json(iterable)),
+            ("body", stmt_to_json(body)),
+        ]),
+        Stmt::Destructure { bracket, names, rest, initializer } => node("Destructure", vec![
+            ("bracket", token(bracket)),
+            ("names", tokens(names)),
+            ("rest", optional(rest.as_ref().map(token))),
+            ("initializer", expr_to_json(initializer)),
+        ]),
+        Stmt::Try { body, name, handler } => node("Try", vec![
+            ("body", program_to_json(body)),
+            ("name", token(name)),
+            ("handler", program_to_json(handler)),
+        ]),
+        Stmt::Throw { keyword, value } => node("Throw", vec![
+            ("keyword", token(keyword)),
+            ("value", expr_to_json(value)),
+        ]),
+    }
+}
+
+pub fn expr_to_json(expr: &Expr) -> Json {
+    match expr {
+        Expr::Binary { left, operator, right } => node("Binary", vec![
+            ("left", expr_to_json(left)),
+            ("operator", token(operator)),
+            ("right", expr_to_json(right)),
+        ]),
+        Expr::Unary { operator, right } => node("Unary", vec![
+            ("operator", token(operator)),
+            ("right", expr_to_json(right)),
+        ]),
+        Expr::Literal { value } => node("Literal", vec![("value", literal(value))]),
+        Expr::Grouping { expression } => node("Grouping", vec![("expression", expr_to_json(expression))]),
+        Expr::Variable { name } => node("Variable", vec![("name", token(name))]),
+        Expr::Assign { name, value } => node("Assign", vec![
+            ("name", token(name)),
+            ("value", expr_to_json(value)),
+        ]),
+        Expr::Logical { left, operator, right } => node("Logical", vec![
+            ("left", expr_to_json(left)),
+            ("operator", token(operator)),
+            ("right", expr_to_json(right)),
+        ]),
+        Expr::Call { callee, paren, arguments } => node("Call", vec![
+            ("callee", expr_to_json(callee)),
+            ("paren", token(paren)),
+            ("arguments", exprs(arguments)),
+        ]),
+        Expr::Loop { keyword, condition, body } => node("Loop", vec![
+            ("keyword", token(keyword)),
+            ("condition", expr_to_json(condition)),
+            ("body", stmt_to_json(body)),
+        ]),
+        Expr::List { elements } => node("List", vec![("elements", exprs(elements))]),
+        Expr::Map { brace, entries } => node("Map", vec![
+            ("brace", token(brace)),
+            ("entries", Json::Array(entries.iter()
+                .map(|(key, value)| Json::Object(vec![("key", expr_to_json(key)), ("value", expr_to_json(value))]))
+                .collect())),
+        ]),
+        Expr::Index { object, bracket, index } => node("Index", vec![
+            ("object", expr_to_json(object)),
+            ("bracket", token(bracket)),
+            ("index", expr_to_json(index)),
+        ]),
+        Expr::IndexSet { object, bracket, index, value } => node("IndexSet", vec![
+            ("object", expr_to_json(object)),
+            ("bracket", token(bracket)),
+            ("index", expr_to_json(index)),
+            ("value", expr_to_json(value)),
+        ]),
+        Expr::Get { object, name } => node("Get", vec![
+            ("object", expr_to_json(object)),
+            ("name", token(name)),
+        ]),
+        Expr::Set { object, name, value } => node("Set", vec![
+            ("object", expr_to_json(object)),
+            ("name", token(name)),
+            ("value", expr_to_json(value)),
+        ]),
+        Expr::OptionalGet { object, name } => node("OptionalGet", vec![
+            ("object", expr_to_json(object)),
+            ("name", token(name)),
+        ]),
+        Expr::Range { start, operator, end } => node("Range", vec![
+            ("start", expr_to_json(start)),
+            ("operator", token(operator)),
+            ("end", expr_to_json(end)),
+        ]),
+        Expr::Let { name, value, body } => node("Let", vec![
+            ("name", token(name)),
+            ("value", expr_to_json(value)),
+            ("body", expr_to_json(body)),
+        ]),
+    }
+}
+
+fn node(kind: &'static str, mut fields: Vec<(&'static str, Json)>) -> Json {
+    fields.insert(0, ("type", Json::String(kind.to_string())));
+    Json::Object(fields)
+}
+
+fn token(token: &Token) -> Json {
+    Json::Object(vec![
+        ("lexeme", Json::String(token.lexeme.clone())),
+        ("line", Json::Int(token.line as i64)),
+        ("column", Json::Int(token.column as i64)),
+    ])
+}
+
+fn tokens(tokens: &[Token]) -> Json {
+    Json::Array(tokens.iter().map(token).collect())
+}
+
+fn exprs(exprs: &[Expr]) -> Json {
+    Json::Array(exprs.iter().map(expr_to_json).collect())
+}
+
+fn optional(json: Option<Json>) -> Json {
+    json.unwrap_or(Json::Null)
+}
+
+fn literal(value: &Option<LiteralValue>) -> Json {
+    match value {
+        Some(LiteralValue::String(s)) => Json::String(s.clone()),
+        Some(LiteralValue::Number(n)) => Json::Number(*n),
+        Some(LiteralValue::Int(n)) => Json::Int(*n),
+        Some(LiteralValue::Boolean(b)) => Json::Bool(*b),
+        Some(LiteralValue::Nil) | None => Json::Null,
+    }
+}
+
+// Compact JSON on one line
+impl std::fmt::Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Int(n) => write!(f, "{}", n),
+            Json::Number(n) if n.is_finite() => write!(f, "{:?}", n), // {:?} keeps the ".0" of 2.0
+            Json::Number(_) => write!(f, "null"), // JSON has no NaN or infinity
+            Json::String(s) => write_string(f, s),
+            Json::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut std::fmt::Formatter<'_>, s: &str) -> std::fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
@@ -0,0 +1,29 @@
+//! rlox: a tree-walk interpreter for Lox, following Crafting Interpreters.
+//!
+//! The pipeline is Scanner (source -> tokens) -> Parser (tokens -> AST)
+//! -> Interpreter (AST -> values and side effects). `main.rs` is only a CLI on top of this.
+
+pub mod token;
+pub mod scanner;
+pub mod error;
+pub mod expr;
+pub mod ast_printer;
+pub mod parser;
+pub mod interpreter;
+pub mod value;
+pub mod stmt;
+pub mod function;
+pub mod native;
+pub mod fold;
+pub mod resolver;
+pub mod json;
+pub mod history;
+pub mod formatter;
+mod environment;
+
+pub use error::ErrorReporter;
+pub use interpreter::{Interpreter, InterpreterSnapshot, RuntimeError, RuntimeErrorKind, StatementFailed};
+pub use parser::Parser;
+pub use scanner::{ScanError, Scanner};
+pub use token::{LiteralValue, Token, TokenType};
+pub use value::Value;
@@ -1,71 +1,129 @@
-mod token;
-mod scanner;
-mod error;
-mod expr;
-mod ast_printer;
-mod parser;
-mod interpreter;
-mod value;
-mod stmt;
-mod environment;
-mod function;
-mod native;
-
-use scanner::Scanner;
-use error::ErrorReporter;
-use expr::Expr;
-use ast_printer::AstPrinter;
-use token::{Token, TokenType, LiteralValue};
-use parser::Parser;
+use rlox::scanner::Scanner;
+use rlox::error::ErrorReporter;
+use rlox::expr::Expr;
+use rlox::ast_printer::AstPrinter;
+use rlox::token::{Token, TokenType, LiteralValue};
+use rlox::parser::Parser;
+use rlox::json;
+use rlox::formatter;
+use rlox::history::History;
+use rlox::resolver::Resolver;
+use rlox::interpreter::{self, Interpreter};
+use rlox::value::Value;
 use std::env;
 use std::fs;
 use std::io::{self, Write};
+use std::path::PathBuf;
 use std::process;
-use interpreter::Interpreter;
+
+// What the command line asked us to do
+enum Command {
+    Prompt { history: Option<PathBuf> }, // where REPL lines are saved, None = nowhere
+    Script { path: String, options: RunOptions },
+    Format { path: String }, // lox fmt file.lox: print the file's code laid out the standard way
+    Test(fn()),
+    Version,
+}
+
+// Flags that change how a script runs. They go before the script path: lox --profile file.lox
+#[derive(Default)]
+struct RunOptions {
+    profile: bool,  // print how many times each kind of node was evaluated
+    dump_env: bool, // print the global variables once the script is done
+    precision: Option<usize>, // decimals shown for non-integer numbers: lox --precision 2 file.lox
+    trace: bool,    // log each statement and expression value to stderr while running
+    max_iterations: usize, // stop with a runtime error after this many loop iterations, 0 = never
+    emit_json: bool, // print the parsed program as JSON instead of running it
+    tab_width: Option<usize>, // columns a tab counts as when working out token columns, default 4
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let args: Vec<String> = env::args().skip(1).collect();
     let mut error_reporter = ErrorReporter::new();
 
-    match args.len() {
-        1 => run_prompt(&mut error_reporter),
-        2 => {
-            if args[1] == "--test-ast" {
-                test_ast_printer();
-                return;
-            }
-            if args[1] == "--test-parser" {
-                test_parser();
-                return;
-            }
-            if args[1] == "--test-interpreter" {
-                test_interpreter();
-                return;
-            }
-            if args[1] == "--test-control-flow" {
-                test_control_flow();
-                return;
-            }
-            if args[1] == "--test-functions" {
-                test_functions();
-                return;
-            }
-            run_file(&args[1], &mut error_reporter);
-        }
-        _ => {
-            println!("Usage: lox [script] or lox --test-ast or lox --test-control-flow");
+    match parse_args(&args) {
+        Some(Command::Prompt { history }) => run_prompt(history, &mut error_reporter),
+        Some(Command::Script { path, options }) => run_file(&path, &options, &mut error_reporter),
+        Some(Command::Format { path }) => format_file(&path, &mut error_reporter),
+        Some(Command::Test(test)) => test(),
+        Some(Command::Version) => println!("{}", version()),
+        None => {
+            println!("Usage: lox --version or lox [--profile] [--dump-env] [--precision N] [--trace] [--max-iterations N] [--emit-json] [--tab-width N] [script] or lox [--repl-history FILE] or lox fmt script or lox --test-ast or lox --test-control-flow");
             process::exit(64);
         }
     }
 }
 
-fn run_file(path: &str, error_reporter: &mut ErrorReporter) {
+fn parse_args(args: &[String]) -> Option<Command> {
+    if args.is_empty() {
+        return Some(Command::Prompt { history: History::default_path() });
+    }
+    if let [flag] = args
+        && let Some(test) = test_suite(flag) {
+        return Some(Command::Test(test));
+    }
+    if let [flag] = args
+        && flag == "--version" {
+        return Some(Command::Version);
+    }
+    if let [command, path] = args
+        && command == "fmt" {
+        return Some(Command::Format { path: path.clone() });
+    }
+
+    let mut options = RunOptions::default();
+    let mut path = None;
+    let mut history = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--profile" => options.profile = true,
+            "--precision" => options.precision = Some(args.next()?.parse().ok()?),
+            "--dump-env" => options.dump_env = true,
+            "--trace" => options.trace = true,
+            "--emit-json" => options.emit_json = true,
+            "--tab-width" => options.tab_width = Some(args.next()?.parse().ok()?),
+            "--max-iterations" => options.max_iterations = args.next()?.parse().ok()?,
+            "--repl-history" => history = Some(PathBuf::from(args.next()?)),
+            flag if flag.starts_with("--") => return None,
+            _ if path.is_none() => path = Some(arg.clone()),
+            _ => return None, // only one script at a time
+        }
+    }
+    match (path, history) {
+        (Some(path), None) => Some(Command::Script { path, options }),
+        (None, Some(history)) => Some(Command::Prompt { history: Some(history) }),
+        _ => None, // history only makes sense for the REPL
+    }
+}
+
+// e.g. "rlox 0.1.0 (a1b2c3d)". The commit comes from build.rs, "unknown" when built outside a git checkout.
+fn version() -> String {
+    format!("rlox {} ({})", env!("CARGO_PKG_VERSION"), env!("RLOX_COMMIT"))
+}
+
+fn test_suite(flag: &str) -> Option<fn()> {
+    match flag {
+        "--test-ast" => Some(test_ast_printer),
+        "--test-parser" => Some(test_parser),
+        "--test-interpreter" => Some(test_interpreter),
+        "--test-control-flow" => Some(test_control_flow),
+        "--test-functions" => Some(test_functions),
+        "--test-eval" => Some(test_eval),
+        _ => None,
+    }
+}
+
+fn run_file(path: &str, options: &RunOptions, error_reporter: &mut ErrorReporter) {
     match fs::read_to_string(path) {
         Ok(source) => {
-            run(source, error_reporter);
+            run_with_options(source, Some(path), options, error_reporter);
             if error_reporter.had_error() {
                 process::exit(65);
             }
+            if error_reporter.had_runtime_error() {
+                process::exit(70);
+            }
         }
         Err(err) => {
             eprintln!("Error reading file: {}", err);
@@ -74,33 +132,102 @@ fn run_file(path: &str, error_reporter: &mut ErrorReporter) {
     }
 }
 
+fn format_file(path: &str, error_reporter: &mut ErrorReporter) {
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(err) => {
+            eprintln!("Error reading file: {}", err);
+            process::exit(66);
+        }
+    };
+    error_reporter.set_source(&source);
+    let mut scanner = Scanner::new(source);
+    let Some(tokens) = scan(&mut scanner, error_reporter) else { process::exit(65) };
+    let Some(statements) = Parser::new(tokens).parse(error_reporter) else { process::exit(65) };
+    print!("{}", formatter::format_program(&statements));
+}
+
 fn run(source: String, error_reporter: &mut ErrorReporter) {
+    run_with_options(source, None, &RunOptions::default(), error_reporter);
+}
+
+fn run_with_options(source: String, path: Option<&str>, options: &RunOptions, error_reporter: &mut ErrorReporter) {
+    error_reporter.set_source(&source);
     let mut scanner = Scanner::new(source);
+    if let Some(tab_width) = options.tab_width {
+        scanner.set_tab_width(tab_width);
+    }
     
-    match scanner.scan_tokens() {
-        Ok(tokens) => {
-            let mut parser = Parser::new(tokens.clone());
-            if let Some(statements) = parser.parse(error_reporter) {
-                let mut interpreter = Interpreter::new();
-                if let Err(err) = interpreter.interpret(&statements) {
-                    if let Some(runtime_err) = err.downcast_ref::<interpreter::RuntimeError>() {
-                        eprintln!("{}", runtime_err);
-                    } else {
-                        eprintln!("Runtime error: {}", err);
-                    }
-                }
-            }
-        }
-        Err(errors) => {
-            eprintln!("{}", errors);
-            error_reporter.report(0, "", "Scanning failed");
+    let Some(tokens) = scan(&mut scanner, error_reporter) else { return };
+    let mut parser = Parser::new(tokens);
+    let Some(statements) = parser.parse(error_reporter) else { return };
+    if options.emit_json {
+        println!("{}", json::program_to_json(&statements));
+        return;
+    }
+    Resolver::new().resolve(&statements, error_reporter);
+    if error_reporter.had_error() {
+        return;
+    }
+    let mut interpreter = Interpreter::new();
+    if let Some(path) = path {
+        interpreter.set_script_path(path);
+    }
+    if options.profile {
+        interpreter.enable_profiling();
+    }
+    interpreter.set_precision(options.precision);
+    interpreter.set_max_iterations(options.max_iterations);
+    if options.trace {
+        interpreter.enable_trace(io::stderr());
+    }
+    if let Err(err) = interpreter.interpret(&statements) {
+        if let Some(runtime_err) = err.downcast_ref::<interpreter::RuntimeError>() {
+            error_reporter.runtime_error(runtime_err);
+        } else {
+            eprintln!("Runtime error: {}", err);
         }
     }
+    if options.profile {
+        print_profile(&interpreter);
+    }
+    if options.dump_env {
+        print_globals(&interpreter);
+    }
+}
+
+// Scan the whole source. On errors, report each one, then parse what did scan anyway so syntax errors further
+// down show up in the same run, and give back None: nothing gets run.
+fn scan(scanner: &mut Scanner, error_reporter: &mut ErrorReporter) -> Option<Vec<Token>> {
+    if let Ok(tokens) = scanner.scan_tokens() {
+        return Some(tokens.clone());
+    }
+    for error in scanner.errors() {
+        error_reporter.report_span(error.line, error.start, error.length, &error.message);
+    }
+    Parser::new(scanner.tokens().to_vec()).parse(error_reporter);
+    None
+}
+
+fn print_profile(interpreter: &Interpreter) {
+    eprintln!("--- Profile ---");
+    for (kind, count) in interpreter.profile() {
+        eprintln!("{:<16}{}", kind, count);
+    }
 }
 
-fn run_prompt(error_reporter: &mut ErrorReporter) {
+fn print_globals(interpreter: &Interpreter) {
+    eprintln!("--- Globals ---");
+    for (name, value) in interpreter.dump_globals() {
+        eprintln!("{} = {}", name, value);
+    }
+}
+
+fn run_prompt(history_path: Option<PathBuf>, error_reporter: &mut ErrorReporter) {
     let mut interpreter = Interpreter::new(); // Create interpreter once
-    
+    interpreter.set_repl_mode(true);
+    let mut history = history_path.map_or_else(History::disabled, History::open);
+
     loop {
         print!("> ");
         io::stdout().flush().unwrap();
@@ -109,6 +236,10 @@ fn run_prompt(error_reporter: &mut ErrorReporter) {
         match io::stdin().read_line(&mut input) {
             Ok(0) => break, // EOF
             Ok(_) => {
+                if let Err(err) = history.add(&input) {
+                    eprintln!("Can't save history, continuing without it: {}", err);
+                    history.disable();
+                }
                 run_repl(input, error_reporter, &mut interpreter); // Pass interpreter
                 error_reporter.reset();
             }
@@ -121,24 +252,18 @@ fn run_prompt(error_reporter: &mut ErrorReporter) {
 }
 
 fn run_repl(source: String, error_reporter: &mut ErrorReporter, interpreter: &mut Interpreter) {
+    error_reporter.set_source(&source);
     let mut scanner = Scanner::new(source);
     
-    match scanner.scan_tokens() {
-        Ok(tokens) => {
-            let mut parser = Parser::new(tokens.clone());
-            if let Some(statements) = parser.parse(error_reporter) {
-                if let Err(err) = interpreter.interpret(&statements) {
-                    if let Some(runtime_err) = err.downcast_ref::<interpreter::RuntimeError>() {
-                        eprintln!("{}", runtime_err);
-                    } else {
-                        eprintln!("Runtime error: {}", err);
-                    }
-                }
-            }
-        }
-        Err(errors) => {
-            eprintln!("{}", errors);
-            error_reporter.report(0, "", "Scanning failed");
+    let Some(tokens) = scan(&mut scanner, error_reporter) else { return };
+    // Typing "x = 1 + 2" without the ';' is fine at the prompt, as long as it's the end of the line
+    let mut parser = Parser::new_with_trailing_expression(tokens);
+    if let Some(statements) = parser.parse(error_reporter)
+        && let Err(err) = interpreter.interpret(&statements) {
+        if let Some(runtime_err) = err.downcast_ref::<interpreter::RuntimeError>() {
+            error_reporter.runtime_error(runtime_err);
+        } else {
+            eprintln!("Runtime error: {}", err);
         }
     }
 }
@@ -341,4 +466,28 @@ fn test_functions() {
         let mut error_reporter = ErrorReporter::new();
         run(error_case.to_string(), &mut error_reporter);
     }
-}
\ No newline at end of file
+}
+
+fn test_eval() {
+    println!("Testing eval_str...");
+
+    let mut interpreter = Interpreter::new();
+
+    let result = interpreter.eval_str("1 + 2").unwrap();
+    println!("eval_str(\"1 + 2\") = {}", result);
+    assert_eq!(result, Value::Number(3.0));
+
+    // Earlier statements run first, the final expression is the result
+    let result = interpreter.eval_str("var a = 10; a * 2;").unwrap();
+    println!("eval_str(\"var a = 10; a * 2;\") = {}", result);
+    assert_eq!(result, Value::Number(20.0));
+
+    // The interpreter keeps its state between calls
+    let result = interpreter.eval_str("a").unwrap();
+    println!("eval_str(\"a\") = {}", result);
+    assert_eq!(result, Value::Number(10.0));
+
+    let result = interpreter.eval_str("1 +");
+    println!("eval_str(\"1 +\") is an error: {}", result.is_err());
+    assert!(result.is_err());
+}
@@ -1,27 +1,410 @@
 use crate::value::Value;
 use crate::interpreter::Interpreter;
-use anyhow::Result;
-use std::time::{SystemTime, UNIX_EPOCH};
+use anyhow::{anyhow, Result};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// Where clock() gets the time from, in seconds. Tests can swap in a fake one with Interpreter::with_clock().
+pub trait ClockSource {
+    fn now(&self) -> f64;
+}
+
+// The real time since the Unix epoch
+pub struct SystemClock;
+
+impl ClockSource for SystemClock {
+    fn now(&self) -> f64 {
+        let duration = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap();
+        duration.as_millis() as f64 / 1000.0
+    }
+}
+
+// So a closure works as a clock: Interpreter::with_clock(|| 42.0)
+impl<F: Fn() -> f64> ClockSource for F {
+    fn now(&self) -> f64 {
+        self()
+    }
+}
+
+// The Rust side of a host function
+pub type HostFn = dyn Fn(&mut Interpreter, Vec<Value>) -> Result<Value>;
+
+// A native function registered by the program embedding rlox, see Interpreter::define_native()
+pub struct HostFunction {
+    pub name: String,
+    pub arity: usize,
+    pub function: Box<HostFn>,
+}
+
+impl std::fmt::Debug for HostFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HostFunction({}/{})", self.name, self.arity)
+    }
+}
+
+// A native looked up as a method, e.g. "abc".upper. The receiver becomes the first argument when called.
+#[derive(Debug)]
+pub struct BoundNative {
+    pub receiver: Value,
+    pub function: NativeFunction,
+}
+
+// How many arguments a native takes. Variadic ones like str() accept any number.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Arity {
+    Fixed(usize),
+    Variadic,
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum NativeFunction {
     Clock,
+    ToUpper,
+    ToLower,
+    Trim,
+    Contains,
+    StartsWith,
+    EndsWith,
+    Len,
+    AssertEq,
+    Format,
+    Hex,
+    EnvCount,
+    Str,
+    GetEnv,
+    SetEnv,
+    ApproxEq,
+    Repr,
+    Clone,
+    IsCallable,
+    Arity,
+    Map,
+    Filter,
+    Reduce,
+    Grouped,
+    Push,
+    Pop,
+    Insert,
+    Remove,
+    Eval,
+    Bench,
 }
 
 impl NativeFunction {
-    pub fn arity(&self) -> usize {
+    // Every native function, used by the interpreter to register them as globals
+    pub const ALL: &'static [NativeFunction] = &[
+        NativeFunction::Clock,
+        NativeFunction::ToUpper,
+        NativeFunction::ToLower,
+        NativeFunction::Trim,
+        NativeFunction::Contains,
+        NativeFunction::StartsWith,
+        NativeFunction::EndsWith,
+        NativeFunction::Len,
+        NativeFunction::AssertEq,
+        NativeFunction::Format,
+        NativeFunction::Hex,
+        NativeFunction::EnvCount,
+        NativeFunction::Str,
+        NativeFunction::GetEnv,
+        NativeFunction::SetEnv,
+        NativeFunction::ApproxEq,
+        NativeFunction::Repr,
+        NativeFunction::Clone,
+        NativeFunction::IsCallable,
+        NativeFunction::Arity,
+        NativeFunction::Map,
+        NativeFunction::Filter,
+        NativeFunction::Reduce,
+        NativeFunction::Grouped,
+        NativeFunction::Push,
+        NativeFunction::Pop,
+        NativeFunction::Insert,
+        NativeFunction::Remove,
+        NativeFunction::Eval,
+        NativeFunction::Bench,
+    ];
+
+    // Methods on strings, "abc".length() is the same as len("abc")
+    pub fn string_method(name: &str) -> Option<NativeFunction> {
+        match name {
+            "length" => Some(NativeFunction::Len),
+            "upper" => Some(NativeFunction::ToUpper),
+            "lower" => Some(NativeFunction::ToLower),
+            "trim" => Some(NativeFunction::Trim),
+            "contains" => Some(NativeFunction::Contains),
+            "startsWith" => Some(NativeFunction::StartsWith),
+            "endsWith" => Some(NativeFunction::EndsWith),
+            _ => None,
+        }
+    }
+
+    pub fn arity(&self) -> Arity {
         match self {
-            NativeFunction::Clock => 0,
+            NativeFunction::Clock | NativeFunction::EnvCount => Arity::Fixed(0),
+            NativeFunction::ToUpper | NativeFunction::ToLower | NativeFunction::Trim | NativeFunction::Len => Arity::Fixed(1),
+            NativeFunction::Hex | NativeFunction::GetEnv | NativeFunction::Repr | NativeFunction::Grouped => Arity::Fixed(1),
+            NativeFunction::Clone | NativeFunction::IsCallable | NativeFunction::Arity => Arity::Fixed(1),
+            NativeFunction::SetEnv | NativeFunction::Map | NativeFunction::Filter | NativeFunction::Bench => Arity::Fixed(2),
+            NativeFunction::Reduce | NativeFunction::Insert => Arity::Fixed(3),
+            NativeFunction::Push | NativeFunction::Remove => Arity::Fixed(2),
+            NativeFunction::Pop | NativeFunction::Eval => Arity::Fixed(1),
+            NativeFunction::ApproxEq => Arity::Fixed(3),
+            NativeFunction::Contains | NativeFunction::StartsWith | NativeFunction::EndsWith => Arity::Fixed(2),
+            NativeFunction::AssertEq | NativeFunction::Format => Arity::Fixed(2),
+            NativeFunction::Str => Arity::Variadic,
         }
     }
 
-    pub fn call(&self, _interpreter: &mut Interpreter, _arguments: Vec<Value>) -> Result<Value> {
+    pub fn call(&self, interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value> {
         match self {
-            NativeFunction::Clock => {
-                let duration = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap();
-                Ok(Value::Number(duration.as_millis() as f64 / 1000.0))
+            NativeFunction::Clock => Ok(Value::Number(interpreter.now())),
+            // to_uppercase/to_lowercase are Unicode-aware, so "straße" becomes "STRASSE"
+            NativeFunction::ToUpper => {
+                let s = self.expect_string(&arguments[0])?;
+                Ok(Value::String(s.to_uppercase()))
+            }
+            NativeFunction::ToLower => {
+                let s = self.expect_string(&arguments[0])?;
+                Ok(Value::String(s.to_lowercase()))
+            }
+            // Only leading/trailing whitespace is removed, inner spaces are kept
+            NativeFunction::Trim => {
+                let s = self.expect_string(&arguments[0])?;
+                Ok(Value::String(s.trim().to_string()))
+            }
+            // An empty needle/prefix/suffix always matches, same as Rust's str methods
+            NativeFunction::Contains => {
+                let haystack = self.expect_string(&arguments[0])?;
+                let needle = self.expect_string(&arguments[1])?;
+                Ok(Value::Boolean(haystack.contains(needle)))
+            }
+            NativeFunction::StartsWith => {
+                let s = self.expect_string(&arguments[0])?;
+                let prefix = self.expect_string(&arguments[1])?;
+                Ok(Value::Boolean(s.starts_with(prefix)))
+            }
+            NativeFunction::EndsWith => {
+                let s = self.expect_string(&arguments[0])?;
+                let suffix = self.expect_string(&arguments[1])?;
+                Ok(Value::Boolean(s.ends_with(suffix)))
+            }
+            // Strings count characters, not bytes. An empty or backwards range has length 0,
+            // one longer than the largest int has the largest int.
+            NativeFunction::Len => {
+                let len = match &arguments[0] {
+                    Value::List(elements) => elements.borrow().len() as i64,
+                    Value::Map(entries) => entries.borrow().len() as i64,
+                    Value::String(s) => s.chars().count() as i64,
+                    Value::Range { start, end, inclusive } => {
+                        let len = end.saturating_sub(*start).saturating_add(if *inclusive { 1 } else { 0 });
+                        len.max(0)
+                    }
+                    _ => return Err(anyhow!("Argument to len() must be a list, map, string or range.")),
+                };
+                Ok(Value::Int(len))
+            }
+            // Uses Lox equality, so assertEq(1, 1.0) passes
+            NativeFunction::AssertEq => {
+                let (actual, expected) = (&arguments[0], &arguments[1]);
+                if actual.is_equal(expected) {
+                    Ok(Value::Nil)
+                } else {
+                    Err(anyhow!("Assertion failed: expected {} but got {}.", expected, actual))
+                }
+            }
+            NativeFunction::EnvCount => Ok(Value::Int(interpreter.env_count() as i64)),
+            // format(3.14159, 2) is "3.14". Rounds to nearest, so format(1.96, 1) is "2.0".
+            NativeFunction::Format => {
+                let n = self.expect_number(&arguments[0])?;
+                let decimals = self.expect_integer(&arguments[1])?;
+                if decimals < 0 {
+                    return Err(anyhow!("Number of decimals for format() can't be negative."));
+                }
+                Ok(Value::String(format!("{:.*}", decimals as usize, n)))
+            }
+            // hex(255) is "ff", and hex(-255) is "-ff"
+            NativeFunction::Hex => {
+                let n = self.expect_integer(&arguments[0])?;
+                let sign = if n < 0 { "-" } else { "" };
+                Ok(Value::String(format!("{}{:x}", sign, n.unsigned_abs())))
+            }
+            // grouped(-1234567.5) is "-1,234,567.5". The number is shown the way print would show it, then the
+            // digits before the decimal point get a comma every three.
+            NativeFunction::Grouped => {
+                let n = self.expect_number(&arguments[0])?;
+                let text = interpreter.stringify(&arguments[0]);
+                // Exponents, inf and NaN are left alone
+                if !n.is_finite() || text.contains(['e', 'E']) {
+                    return Ok(Value::String(text));
+                }
+                let (sign, unsigned) = text.split_at(if text.starts_with('-') { 1 } else { 0 });
+                let (whole, fraction) = unsigned.split_at(unsigned.find('.').unwrap_or(unsigned.len()));
+                let mut grouped = String::from(sign);
+                for (i, digit) in whole.chars().enumerate() {
+                    if i > 0 && (whole.len() - i) % 3 == 0 {
+                        grouped.push(',');
+                    }
+                    grouped.push(digit);
+                }
+                grouped.push_str(fraction);
+                Ok(Value::String(grouped))
+            }
+            // str("x=", 3) is "x=3", each argument shown the same way print would show it
+            NativeFunction::Str => {
+                let parts: Vec<String> = arguments.iter().map(|value| interpreter.stringify(value)).collect();
+                Ok(Value::String(parts.concat()))
+            }
+            // approxEq(0.1 + 0.2, 0.3, 1e-9) is true. The tolerance is absolute, not relative.
+            NativeFunction::ApproxEq => {
+                let a = self.expect_number(&arguments[0])?;
+                let b = self.expect_number(&arguments[1])?;
+                let epsilon = self.expect_number(&arguments[2])?;
+                Ok(Value::Boolean((a - b).abs() <= epsilon))
+            }
+            // Strings come back quoted and escaped, the way they'd be written in Lox source: repr("a\nb") shows
+            // a backslash and an n, not a line break. Functions show their arity too. Other values look the
+            // same as with print.
+            NativeFunction::Repr => match &arguments[0] {
+                Value::String(s) => {
+                    let mut repr = String::from("\"");
+                    for c in s.chars() {
+                        match c {
+                            '\n' => repr.push_str("\\n"),
+                            '\t' => repr.push_str("\\t"),
+                            '\r' => repr.push_str("\\r"),
+                            '"' => repr.push_str("\\\""),
+                            '\\' => repr.push_str("\\\\"),
+                            c => repr.push(c),
+                        }
+                    }
+                    repr.push('"');
+                    Ok(Value::String(repr))
+                }
+                Value::Function(function) => Ok(Value::String(function.signature())),
+                other => Ok(Value::String(interpreter.stringify(other))),
+            },
+            NativeFunction::Clone => Ok(deep_clone(&arguments[0], &mut HashMap::new())),
+            NativeFunction::IsCallable => Ok(Value::Boolean(matches!(
+                arguments[0],
+                Value::Function(_) | Value::NativeFunction(_) | Value::BoundNative(_) | Value::HostFunction(_)
+            ))),
+            // map([1, 2, 3], double) is [2, 4, 6]. The callbacks see a snapshot of the list, so changing
+            // the list from inside one doesn't change what gets visited.
+            NativeFunction::Map => {
+                let elements = self.expect_list(&arguments[0])?;
+                let mut results = Vec::with_capacity(elements.len());
+                for element in elements {
+                    results.push(interpreter.call_value(arguments[1].clone(), vec![element])?);
+                }
+                Ok(Value::List(Rc::new(RefCell::new(results))))
+            }
+            NativeFunction::Filter => {
+                let elements = self.expect_list(&arguments[0])?;
+                let mut kept = Vec::new();
+                for element in elements {
+                    if interpreter.call_value(arguments[1].clone(), vec![element.clone()])?.is_truthy() {
+                        kept.push(element);
+                    }
+                }
+                Ok(Value::List(Rc::new(RefCell::new(kept))))
+            }
+            // bench(f, 1000) calls f() 1000 times and gives back the seconds that took in total.
+            // Unlike clock() this always reads the real, monotonic time, so a fake clock can't skew it.
+            NativeFunction::Bench => {
+                if !matches!(arguments[0], Value::Function(_) | Value::NativeFunction(_) | Value::BoundNative(_) | Value::HostFunction(_)) {
+                    return Err(anyhow!("Argument to bench() must be a function."));
+                }
+                let iterations = self.expect_integer(&arguments[1])?;
+                if iterations < 0 {
+                    return Err(anyhow!("Can't bench a negative number of iterations."));
+                }
+                let start = Instant::now();
+                for _ in 0..iterations {
+                    interpreter.call_value(arguments[0].clone(), Vec::new())?;
+                }
+                Ok(Value::Number(start.elapsed().as_secs_f64()))
+            }
+            // eval("1 + 2") is 3. The code runs in the caller's scope, so it can read and define its variables.
+            NativeFunction::Eval => {
+                let source = self.expect_string(&arguments[0])?;
+                interpreter.eval_nested(source)
+            }
+            // push, pop, insert and remove change the list in place, so every variable holding it sees the change
+            NativeFunction::Push => {
+                let list = self.expect_shared_list(&arguments[0])?;
+                list.borrow_mut().push(arguments[1].clone());
+                Ok(Value::Nil)
+            }
+            NativeFunction::Pop => {
+                let list = self.expect_shared_list(&arguments[0])?;
+                let popped = list.borrow_mut().pop();
+                popped.ok_or_else(|| anyhow!("Can't pop from an empty list."))
+            }
+            // insert(list, len(list), x) is the same as push(list, x)
+            NativeFunction::Insert => {
+                let list = self.expect_shared_list(&arguments[0])?;
+                let len = list.borrow().len();
+                let index = self.expect_index(&arguments[1], len + 1, len)?;
+                list.borrow_mut().insert(index, arguments[2].clone());
+                Ok(Value::Nil)
+            }
+            NativeFunction::Remove => {
+                let list = self.expect_shared_list(&arguments[0])?;
+                let len = list.borrow().len();
+                let index = self.expect_index(&arguments[1], len, len)?;
+                let removed = list.borrow_mut().remove(index);
+                Ok(removed)
+            }
+            // reduce([1, 2, 3], add, 0) is add(add(add(0, 1), 2), 3)
+            NativeFunction::Reduce => {
+                let elements = self.expect_list(&arguments[0])?;
+                let mut accumulator = arguments[2].clone();
+                for element in elements {
+                    accumulator = interpreter.call_value(arguments[1].clone(), vec![accumulator, element])?;
+                }
+                Ok(accumulator)
+            }
+            // How many arguments a call expects. nil for natives like str() that take any number.
+            NativeFunction::Arity => {
+                let arity = match &arguments[0] {
+                    Value::Function(function) => Arity::Fixed(function.arity()),
+                    Value::HostFunction(host) => Arity::Fixed(host.arity),
+                    Value::NativeFunction(native) => native.arity(),
+                    // "abc".contains takes one argument, the receiver is already there
+                    Value::BoundNative(bound) => match bound.function.arity() {
+                        Arity::Fixed(with_receiver) => Arity::Fixed(with_receiver - 1),
+                        Arity::Variadic => Arity::Variadic,
+                    },
+                    _ => return Err(anyhow!("Argument to arity() must be a function.")),
+                };
+                Ok(match arity {
+                    Arity::Fixed(n) => Value::Int(n as i64),
+                    Arity::Variadic => Value::Nil,
+                })
+            }
+            // nil when the variable isn't set (or isn't valid Unicode)
+            NativeFunction::GetEnv => {
+                let name = self.expect_string(&arguments[0])?;
+                Ok(std::env::var(name).map_or(Value::Nil, Value::String))
+            }
+            NativeFunction::SetEnv => {
+                let name = self.expect_string(&arguments[0])?;
+                let value = self.expect_string(&arguments[1])?;
+                // set_var panics on these, so turn them into a Lox error instead
+                if name.is_empty() || name.contains(['=', '\0']) {
+                    return Err(anyhow!("Invalid environment variable name '{}'.", name));
+                }
+                if value.contains('\0') {
+                    return Err(anyhow!("Environment variable value can't contain a NUL character."));
+                }
+                // SAFETY: the interpreter is single-threaded, so nothing else reads the environment meanwhile
+                unsafe { std::env::set_var(name, value) };
+                Ok(Value::Nil)
             }
         }
     }
@@ -29,6 +412,119 @@ impl NativeFunction {
     pub fn name(&self) -> &str {
         match self {
             NativeFunction::Clock => "clock",
+            NativeFunction::ToUpper => "toUpper",
+            NativeFunction::ToLower => "toLower",
+            NativeFunction::Trim => "trim",
+            NativeFunction::Contains => "contains",
+            NativeFunction::StartsWith => "startsWith",
+            NativeFunction::EndsWith => "endsWith",
+            NativeFunction::Len => "len",
+            NativeFunction::AssertEq => "assertEq",
+            NativeFunction::Format => "format",
+            NativeFunction::Hex => "hex",
+            NativeFunction::EnvCount => "envCount",
+            NativeFunction::Str => "str",
+            NativeFunction::GetEnv => "getenv",
+            NativeFunction::SetEnv => "setenv",
+            NativeFunction::ApproxEq => "approxEq",
+            NativeFunction::Repr => "repr",
+            NativeFunction::Clone => "clone",
+            NativeFunction::IsCallable => "isCallable",
+            NativeFunction::Arity => "arity",
+            NativeFunction::Map => "map",
+            NativeFunction::Filter => "filter",
+            NativeFunction::Reduce => "reduce",
+            NativeFunction::Grouped => "grouped",
+            NativeFunction::Push => "push",
+            NativeFunction::Pop => "pop",
+            NativeFunction::Insert => "insert",
+            NativeFunction::Remove => "remove",
+            NativeFunction::Eval => "eval",
+            NativeFunction::Bench => "bench",
+        }
+    }
+
+    fn expect_number(&self, value: &Value) -> Result<f64> {
+        match value {
+            Value::Int(n) => Ok(*n as f64),
+            Value::Number(n) => Ok(*n),
+            _ => Err(anyhow!("Argument to {}() must be a number.", self.name())),
+        }
+    }
+
+    // Whole floats like 2.0 count as integers too
+    fn expect_integer(&self, value: &Value) -> Result<i64> {
+        match value {
+            Value::Int(n) => Ok(*n),
+            Value::Number(n) if n.fract() == 0.0 => Ok(*n as i64),
+            _ => Err(anyhow!("Argument to {}() must be an integer.", self.name())),
+        }
+    }
+
+    // A copy of the elements, so calling back into Lox while going through them can't hit a borrowed RefCell
+    fn expect_list(&self, value: &Value) -> Result<Vec<Value>> {
+        match value {
+            Value::List(elements) => Ok(elements.borrow().clone()),
+            _ => Err(anyhow!("Argument to {}() must be a list.", self.name())),
+        }
+    }
+
+    // The list itself, for natives that change it
+    fn expect_shared_list<'a>(&self, value: &'a Value) -> Result<&'a Rc<RefCell<Vec<Value>>>> {
+        match value {
+            Value::List(elements) => Ok(elements),
+            _ => Err(anyhow!("Argument to {}() must be a list.", self.name())),
+        }
+    }
+
+    // An integer index below `limit`. `len` is only for the error message.
+    fn expect_index(&self, value: &Value, limit: usize, len: usize) -> Result<usize> {
+        let index = self.expect_integer(value)?;
+        if index < 0 || index as usize >= limit {
+            return Err(anyhow!("List index {} is out of range for length {}.", index, len));
+        }
+        Ok(index as usize)
+    }
+
+    fn expect_string<'a>(&self, value: &'a Value) -> Result<&'a str> {
+        match value {
+            Value::String(s) => Ok(s),
+            _ => Err(anyhow!("Argument to {}() must be a string.", self.name())),
         }
     }
-}
\ No newline at end of file
+}
+
+/*
+A copy of a list or map that shares nothing with the original, all the way down:
+  var b = clone(a); b[0][0] = 99;   // a is unchanged
+`copies` maps each list/map already copied (by address) to its copy, so a list that contains itself
+becomes a new list that contains itself, and a list reached twice is copied once.
+Everything else isn't mutable from Lox, so it's returned as is.
+*/
+fn deep_clone(value: &Value, copies: &mut HashMap<usize, Value>) -> Value {
+    match value {
+        Value::List(elements) => {
+            let address = Rc::as_ptr(elements) as usize;
+            if let Some(copy) = copies.get(&address) {
+                return copy.clone();
+            }
+            let copy = Rc::new(RefCell::new(Vec::new()));
+            copies.insert(address, Value::List(copy.clone()));
+            let elements: Vec<Value> = elements.borrow().iter().map(|element| deep_clone(element, copies)).collect();
+            *copy.borrow_mut() = elements;
+            Value::List(copy)
+        }
+        Value::Map(entries) => {
+            let address = Rc::as_ptr(entries) as usize;
+            if let Some(copy) = copies.get(&address) {
+                return copy.clone();
+            }
+            let copy = Rc::new(RefCell::new(Default::default()));
+            copies.insert(address, Value::Map(copy.clone()));
+            let entries = entries.borrow().iter().map(|(key, value)| (key.clone(), deep_clone(value, copies))).collect();
+            *copy.borrow_mut() = entries;
+            Value::Map(copy)
+        }
+        other => other.clone(),
+    }
+}
@@ -10,17 +10,23 @@ use crate::expr::Expr;
 use crate::stmt::Stmt;
 use crate::token::{Token, TokenType, LiteralValue};
 use crate::error::ErrorReporter;
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize, // point to the next token waiting to be parsed
+    allow_trailing_expression: bool, // let the last expression statement omit its ';'
+    loop_depth: usize, // how many loops we are inside of, so 'break' outside a loop is an error
+    errors: Vec<ParseError>, // everything reported by the last parse(), for callers that want more than the printout
 }
 
 #[derive(Debug)]
 pub struct ParseError {
     pub message: String,
     pub line: usize,
+    pub start: usize,  // Byte offset of the offending token
+    pub length: usize, // Length of its lexeme, so the error can underline all of it
+    pub expected: Vec<TokenType>, // Tokens that would have been valid here, empty when we can't tell
 }
 
 impl std::fmt::Display for ParseError {
@@ -51,6 +57,17 @@ impl Parser {
         Self {
             tokens,
             current: 0,
+            allow_trailing_expression: false,
+            loop_depth: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    // For eval-style snippets like "1 + 2" where the final expression has no ';'
+    pub fn new_with_trailing_expression(tokens: Vec<Token>) -> Self {
+        Self {
+            allow_trailing_expression: true,
+            ..Self::new(tokens)
         }
     }
 
@@ -59,16 +76,18 @@ impl Parser {
         // Return Vec<Stmt> instead of Expr
         let mut statements = Vec::new();
         while !self.is_at_end() {
+            let statement_start = self.current;
             match self.declaration() {
                 Ok(stmt) => statements.push(stmt),
                 Err(err) => {
-                    if let Some(parse_err) = err.downcast_ref::<ParseError>() {
-                        error_reporter.report(parse_err.line, "", &parse_err.message);
-                    }
-                    else {
-                        error_reporter.report(0, "", &err.to_string());
+                    match err.downcast::<ParseError>() {
+                        Ok(parse_err) => {
+                            error_reporter.report_span(parse_err.line, parse_err.start, parse_err.length, &parse_err.message);
+                            self.errors.push(parse_err);
+                        }
+                        Err(err) => error_reporter.report(0, "", &err.to_string()),
                     }
-                    self.synchronize();
+                    self.synchronize(statement_start);
                 }
             }
         }
@@ -76,6 +95,19 @@ impl Parser {
         else{ Some(statements) }
     }
 
+    // Parse exactly one expression, like "1 + 2 * 3". Anything after it (even a ';') is an error.
+    pub fn parse_expression(&mut self) -> Result<Expr> {
+        let expr = self.expression()?;
+        if !self.is_at_end() {
+            return Err(self.error(self.peek(), "Expect end of expression."));
+        }
+        Ok(expr)
+    }
+
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
     fn declaration(&mut self) -> Result<Stmt> {
         if self.match_tokens(&[TokenType::Fun]) {
             self.function("function")
@@ -83,6 +115,9 @@ impl Parser {
         else if self.match_tokens(&[TokenType::Var]) { // Reminder:match_tokens already moves away from "var"
             self.var_declaration()
         } 
+        else if self.match_tokens(&[TokenType::Import]) {
+            self.import_declaration()
+        }
         else if self.match_tokens(&[TokenType::LeftBrace]){
             Ok(Stmt::block(self.block()?))
         }
@@ -92,7 +127,7 @@ impl Parser {
     }
 
     fn function(&mut self, _kind: &str) -> Result<Stmt> { // _kind is kept to follow the book. Unused now!
-        let name = self.consume(TokenType::Identifier, "Expect function name.")?.clone();
+        let name = self.declared_name("Expect function name.")?;
         self.consume(TokenType::LeftParen, "Expect '(' after function name.")?;
 
         let mut parameters = Vec::new();
@@ -101,15 +136,21 @@ impl Parser {
                 if parameters.len() >= 255 {
                     return Err(self.error(self.peek(), "Can't have more than 255 parameters."));
                 }   
-                parameters.push(self.consume(TokenType::Identifier, "Expect parameter name.")?.clone());
+                parameters.push(self.declared_name("Expect parameter name.")?);
                 if !self.match_tokens(&[TokenType::Comma]) {
                     break;
                 }
             }
         }
-        self.consume(TokenType::RightParen, "Expect ')' after parameters.")?;
+        self.consume_expecting(TokenType::RightParen, &[TokenType::Comma], "Expect ')' after parameters.")?;
         self.consume(TokenType::LeftBrace, "Expect '{' before function body.")?;
-        let body = self.block()?;
+
+        // A loop around the declaration doesn't count inside the body: while (true) { fun f() { break; } }
+        let enclosing_loop_depth = self.loop_depth;
+        self.loop_depth = 0;
+        let body = self.block();
+        self.loop_depth = enclosing_loop_depth;
+        let body = body?;
 
         Ok(Stmt::function(name, parameters, body))
     }
@@ -147,17 +188,65 @@ impl Parser {
     fn while_statement(&mut self) -> Result<Stmt> {
         // TODO: Implement this
         // Similar to if, but simpler - just condition and body
+        let keyword = self.previous().clone();
+        let (condition, body) = self.while_clauses()?;
+        Ok(Stmt::while_stmt(keyword, condition, body))
+    }
+
+    // "(" condition ")" body, shared by the while statement and the while expression
+    fn while_clauses(&mut self) -> Result<(Expr, Stmt)> {
         self.consume(TokenType::LeftParen, "Expect '(' after 'while'.")?;
         let condition = self.expression()?;
         self.consume(TokenType::RightParen, "Expect ')' after while condition.")?;
-        let body = self.statement()?;
+        let body = self.loop_body()?;
+
+        Ok((condition, body))
+    }
+
+    fn loop_body(&mut self) -> Result<Stmt> {
+        self.loop_depth += 1;
+        let body = self.statement();
+        self.loop_depth -= 1;
+        body
+    }
+
+    fn throw_statement(&mut self) -> Result<Stmt> {
+        let keyword = self.previous().clone();
+        let value = self.expression()?;
+        self.consume(TokenType::Semicolon, "Expect ';' after thrown value.")?;
+        Ok(Stmt::throw(keyword, value))
+    }
+
+    fn try_statement(&mut self) -> Result<Stmt> {
+        self.consume(TokenType::LeftBrace, "Expect '{' after 'try'.")?;
+        let body = self.block()?;
+        self.consume(TokenType::Catch, "Expect 'catch' after try block.")?;
+        self.consume(TokenType::LeftParen, "Expect '(' after 'catch'.")?;
+        let name = self.declared_name("Expect error variable name.")?;
+        self.consume(TokenType::RightParen, "Expect ')' after error variable name.")?;
+        self.consume(TokenType::LeftBrace, "Expect '{' before catch block.")?;
+        let handler = self.block()?;
+        Ok(Stmt::try_stmt(body, name, handler))
+    }
 
-        Ok(Stmt::while_stmt(condition, body))
+    fn for_in_statement(&mut self) -> Result<Stmt> {
+        let name = self.declared_name("Expect loop variable name.")?;
+        self.consume(TokenType::In, "Expect 'in' after loop variable.")?;
+        let iterable = self.expression()?;
+        self.consume(TokenType::RightParen, "Expect ')' after for-in clause.")?;
+        let body = self.loop_body()?;
+        Ok(Stmt::for_in(name, iterable, body))
     }
 
     fn for_statement(&mut self) -> Result<Stmt> {
+        let keyword = self.previous().clone();
         self.consume(TokenType::LeftParen, "Expect '(' after 'for'.")?;
 
+        // for (name in iterable) body
+        if self.check(&TokenType::Identifier) && self.check_next(&TokenType::In) {
+            return self.for_in_statement();
+        }
+
         // check if the for loop has an initializer: for(var i=0;...)
         let initializer = if self.match_tokens(&[TokenType::Semicolon]){ 
             // for (; i < 10; i = i + 1) { ... }
@@ -189,38 +278,23 @@ impl Parser {
         };
         self.consume(TokenType::RightParen, "Expect ')' after for clauses.")?;
 
-        let mut body = self.statement()?;
-
-        // Do the transformation from for to while loop
-        // Start from creating a loop block -> pack a loop block with the condition 
-        // -> pack the while loop with initialization
+        let body = self.loop_body()?;
 
-        // start from creating a "block" containing loop body following by increment
-        if let Some(increment_expr) = increment {
-            body = Stmt::block(vec![
-                body,
-                Stmt::expression(increment_expr),
-            ]);
-        }
-        
-        // Create the while loop by packing a condition and body together
-        let condition_expr = condition.unwrap_or_else(|| {
-            Expr::literal(Some(LiteralValue::Boolean(true))) // No condition means "while true {...}"
-        });
-        body = Stmt::while_stmt(condition_expr, body);
-
-        // If there's an initializer, wrap everything in a block
-        if let Some(init) = initializer {
-            body = Stmt::block(vec![init, body]);
-        }
-
-        Ok(body)
+        // Not desugared into a while loop: the interpreter gives each iteration a fresh copy of the loop
+        // variables, so closures made in the body don't all share one i
+        Ok(Stmt::for_stmt(keyword, initializer, condition, increment, body))
     }
 
     fn statement(&mut self) -> Result<Stmt> {
         if self.match_tokens(&[TokenType::Return]) {
             self.return_statement()
         }
+        else if self.match_tokens(&[TokenType::Break]) {
+            self.break_statement()
+        }
+        else if self.match_tokens(&[TokenType::Continue]) {
+            self.continue_statement()
+        }
         else if self.match_tokens(&[TokenType::Print]) {
             self.print_statement()
         } 
@@ -238,6 +312,15 @@ impl Parser {
         else if self.match_tokens(&[TokenType::For]){
             self.for_statement()
         }
+        else if self.match_tokens(&[TokenType::Try]) {
+            self.try_statement()
+        }
+        else if self.match_tokens(&[TokenType::Throw]) {
+            self.throw_statement()
+        }
+        else if self.match_tokens(&[TokenType::Semicolon]) {
+            Ok(Stmt::Empty)
+        }
         else {
             self.expression_statement()
         }
@@ -254,23 +337,54 @@ impl Parser {
         Ok(Stmt::return_stmt(keyword, value))
     }
 
+    fn break_statement(&mut self) -> Result<Stmt> {
+        let keyword = self.previous().clone();
+        if self.loop_depth == 0 {
+            return Err(self.error(&keyword, "Can't use 'break' outside of a loop."));
+        }
+        let value = if !self.check(&TokenType::Semicolon) {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+        self.consume(TokenType::Semicolon, "Expect ';' after 'break'.")?;
+        Ok(Stmt::break_stmt(keyword, value))
+    }
+
+    fn continue_statement(&mut self) -> Result<Stmt> {
+        let keyword = self.previous().clone();
+        if self.loop_depth == 0 {
+            return Err(self.error(&keyword, "Can't use 'continue' outside of a loop."));
+        }
+        self.consume(TokenType::Semicolon, "Expect ';' after 'continue'.")?;
+        Ok(Stmt::continue_stmt(keyword))
+    }
+
     fn print_statement(&mut self) -> Result<Stmt> {
         // TODO: 
         // Parse expression after "print"
         // Consume semicolon
         // Return Stmt::print()
-        let value = self.expression()?;
-        self.consume(TokenType::Semicolon, "Expect ';' after expression.")?;
-        Ok(Stmt::print(value))
+        // Each value is a full expression, so a future comma operator would need parentheses here
+        let mut values = vec![self.expression()?];
+        while self.match_tokens(&[TokenType::Comma]) {
+            values.push(self.expression()?);
+        }
+        self.consume_expecting(TokenType::Semicolon, &[TokenType::Comma], "Expect ';' after expression.")?;
+        Ok(Stmt::print(values))
     }
 
     fn var_declaration(&mut self) -> Result<Stmt> {
+        if self.match_tokens(&[TokenType::LeftBracket]) {
+            return self.destructure_declaration();
+        }
+
         // TODO:
         // Expect identifier for variable name
         // If "=" found, parse initializer expression
         // Consume semicolon
         // Return Stmt::var()
-        let name = self.consume(TokenType::Identifier, "Expect variable name.")?.clone();
+        let name = self.declared_name("Expect variable name.")?;
         
         let initializer = if self.match_tokens(&[TokenType::Equal]) {
             Some(self.expression()?)
@@ -278,8 +392,46 @@ impl Parser {
             None
         };
 
+        let alternatives: &[TokenType] = if initializer.is_none() { &[TokenType::Equal] } else { &[] };
+        self.consume_expecting(TokenType::Semicolon, alternatives, "Expect ';' after variable declaration.")?;
+        Ok(Stmt::var(name, initializer))
+    }
+
+    // var [a, b, ...rest] = list; (the "var [" is already consumed)
+    fn destructure_declaration(&mut self) -> Result<Stmt> {
+        let bracket = self.previous().clone();
+        let mut names = Vec::new();
+        let mut rest = None;
+        if !self.check(&TokenType::RightBracket) {
+            loop {
+                if self.match_tokens(&[TokenType::DotDotDot]) {
+                    // The rest must come last, so no ',' can follow it
+                    rest = Some(self.declared_name("Expect variable name after '...'.")?);
+                    break;
+                }
+                names.push(self.declared_name("Expect variable name in list pattern.")?);
+                if !self.match_tokens(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+        self.consume_expecting(TokenType::RightBracket, &[TokenType::Comma], "Expect ']' after list pattern.")?;
+        self.consume(TokenType::Equal, "Expect '=' after list pattern.")?;
+        let initializer = self.expression()?;
         self.consume(TokenType::Semicolon, "Expect ';' after variable declaration.")?;
-        Ok(Stmt::var(name.clone(), initializer))
+        Ok(Stmt::destructure(bracket, names, rest, initializer))
+    }
+
+    fn import_declaration(&mut self) -> Result<Stmt> {
+        let keyword = self.previous().clone();
+        let path = self.consume(TokenType::String, "Expect file path string after 'import'.")?.clone();
+        let alias = if self.match_tokens(&[TokenType::As]) {
+            Some(self.declared_name("Expect module name after 'as'.")?)
+        } else {
+            None
+        };
+        self.consume(TokenType::Semicolon, "Expect ';' after import.")?;
+        Ok(Stmt::import(keyword, path, alias))
     }
 
     fn expression_statement(&mut self) -> Result<Stmt> {
@@ -288,6 +440,11 @@ impl Parser {
         // Consume semicolon  
         // Return Stmt::expression()
         let expr = self.expression()?;
+        // The final expression of an eval snippet or REPL line may leave out its ';', also when only the
+        // blocks around it close after it: { x = 1 + 2 }. Scripts always need it.
+        if self.allow_trailing_expression && self.only_closing_braces_left() {
+            return Ok(Stmt::expression(expr));
+        }
         self.consume(TokenType::Semicolon, "Expect ';' after value.")?;
         Ok(Stmt::expression(expr))
     }
@@ -303,6 +460,14 @@ impl Parser {
                 return Ok(Expr::assign(name, value));
             }
 
+            if let Expr::Index { object, bracket, index } = expr {
+                return Ok(Expr::index_set(*object, bracket, *index, value));
+            }
+
+            if let Expr::Get { object, name } = expr {
+                return Ok(Expr::set(*object, name, value));
+            }
+
             return Err(self.error(&equals, "Invalid assignment target."));
         }
 
@@ -346,8 +511,8 @@ impl Parser {
         let mut expr = self.comparison()?;
 
         while self.match_tokens(&[TokenType::BangEqual, TokenType::EqualEqual]) {
-            let mut operator = self.previous().clone();
-            let mut right_expr = self.comparison()?;
+            let operator = self.previous().clone();
+            let right_expr = self.comparison()?;
             expr = Expr::binary(expr, operator, right_expr);
         }
         Ok(expr) 
@@ -355,38 +520,69 @@ impl Parser {
 
     fn comparison(&mut self) -> Result<Expr> {
         // TODO: Similar to equality, but for >, >=, <, <=
-        let mut expr = self.term()?;
-        while self.match_tokens(&[TokenType::Greater, TokenType::GreaterEqual, TokenType::Less, TokenType::LessEqual]) {
-            let mut operator = self.previous().clone();
-            let right_expr = self.term()?;
+        let comparisons = [TokenType::Greater, TokenType::GreaterEqual, TokenType::Less, TokenType::LessEqual];
+        let mut expr = self.range()?;
+        if self.match_tokens(&comparisons) {
+            let operator = self.previous().clone();
+            let right_expr = self.range()?;
             expr = Expr::binary(expr, operator, right_expr);
+
+            // 1 < x < 10 would compare the boolean (1 < x) with 10, which is never what was meant
+            if self.check_any(&comparisons) {
+                return Err(self.error(self.peek(), "Chained comparisons are not supported; use 'and'."));
+            }
         }
 
-        return Ok(expr);
+        Ok(expr)
+    }
+
+    // a..b or a..=b, binding looser than arithmetic so 0..n - 1 works. Ranges don't chain: 1..2..3 is an error.
+    fn range(&mut self) -> Result<Expr> {
+        let expr = self.term()?;
+        if self.match_tokens(&[TokenType::DotDot, TokenType::DotDotEqual]) {
+            let operator = self.previous().clone();
+            let end = self.term()?;
+            return Ok(Expr::range(expr, operator, end));
+        }
+        Ok(expr)
     }
 
     fn term(&mut self) -> Result<Expr> {
         // TODO: Handle + and -
         let mut expr = self.factor()?;
         while self.match_tokens(&[TokenType::Plus, TokenType::Minus]) {
-            let mut operator = self.previous().clone();
+            let operator = self.previous().clone();
             let right_expr = self.factor()?;
             expr = Expr::binary(expr, operator, right_expr);
         }
 
-        return Ok(expr);
+        Ok(expr)
     }
 
     fn factor(&mut self) -> Result<Expr> {
         // TODO: Handle * and /
-        let mut expr = self.unary()?;
+        let mut expr = self.power()?;
         while self.match_tokens(&[TokenType::Star, TokenType::Slash]) {
-            let mut operator = self.previous().clone();
-            let right_expr = self.unary()?;
+            let operator = self.previous().clone();
+            let right_expr = self.power()?;
             expr = Expr::binary(expr, operator, right_expr);
         }
 
-        return Ok(expr);
+        Ok(expr)
+    }
+
+    /*
+    Exponents group from the right: 2 ** 3 ** 2 is 2 ** (3 ** 2) = 512.
+    Unary minus binds tighter, so -2 ** 2 is (-2) ** 2 = 4. Write -(2 ** 2) to get -4.
+    */
+    fn power(&mut self) -> Result<Expr> {
+        let expr = self.unary()?;
+        if self.match_tokens(&[TokenType::StarStar]) {
+            let operator = self.previous().clone();
+            let right_expr = self.power()?; // Recursing (instead of looping) is what makes it right-associative
+            return Ok(Expr::binary(expr, operator, right_expr));
+        }
+        Ok(expr)
     }
 
     fn unary(&mut self) -> Result<Expr> {
@@ -394,11 +590,11 @@ impl Parser {
         // If we see ! or -, consume it and recursively call unary()
         // Otherwise, call primary()
         if self.match_tokens(&[TokenType::Bang, TokenType::Minus]){
-            let mut operator = self.previous().clone();
-            let mut right_expr = self.unary()?;
-            return Ok(Expr::unary(operator, right_expr));
+            let operator = self.previous().clone();
+            let right_expr = self.unary()?;
+            Ok(Expr::unary(operator, right_expr))
         }
-        else { return self.call();}
+        else { self.call() }
     }
 
     fn call(&mut self) -> Result<Expr>{ // can handle f(), g()(), h(3)()(), ...
@@ -407,6 +603,16 @@ impl Parser {
         loop {
             if self.match_tokens(&[TokenType::LeftParen]){ // Use match_token() because it advances the pointer, unlike check()
                 expr = self.finish_call(expr)?;
+            } else if self.match_tokens(&[TokenType::LeftBracket]) { // a[0], a[0][1], f()[0], ...
+                let index = self.expression()?;
+                let bracket = self.consume(TokenType::RightBracket, "Expect ']' after index.")?.clone();
+                expr = Expr::index(expr, bracket, index);
+            } else if self.match_tokens(&[TokenType::Dot]) { // m.square, m.square(3)
+                let name = self.consume(TokenType::Identifier, "Expect property name after '.'.")?.clone();
+                expr = Expr::get(expr, name);
+            } else if self.match_tokens(&[TokenType::QuestionDot]) { // m?.square
+                let name = self.consume(TokenType::Identifier, "Expect property name after '?.'.")?.clone();
+                expr = Expr::optional_get(expr, name);
             } else{
                 break;
             }
@@ -430,7 +636,7 @@ impl Parser {
             }
         }
 
-        let paren = self.consume(TokenType::RightParen, "Expect ')' after arguments.")?.clone();
+        let paren = self.consume_expecting(TokenType::RightParen, &[TokenType::Comma], "Expect ')' after arguments.")?.clone();
         Ok(Expr::call(callee, paren, arguments))
     }
 
@@ -439,7 +645,14 @@ impl Parser {
         // This is where you handle the "leaves" of the expression tree
 
         if self.match_tokens(&[TokenType::Identifier]) {
-            return Ok(Expr::variable(self.previous().clone()));
+            // Infinity and NaN read like names but are number literals. declared_name() refuses them as names,
+            // so a variable called Infinity can't be declared and then silently never read.
+            let literal = match self.previous().lexeme.as_str() {
+                "Infinity" => f64::INFINITY,
+                "NaN" => f64::NAN,
+                _ => return Ok(Expr::variable(self.previous().clone())),
+            };
+            return Ok(Expr::literal(Some(LiteralValue::Number(literal))));
         }
 
         if self.match_tokens(&[TokenType::False]) {
@@ -464,7 +677,82 @@ impl Parser {
             return Ok(Expr::grouping(expr));
         }
 
-        Err(self.error(self.peek(), "Expect expression."))
+        if self.match_tokens(&[TokenType::LeftBracket]) {
+            return self.list();
+        }
+
+        // A '{' at the start of a statement is a block, so map literals only appear inside expressions
+        if self.match_tokens(&[TokenType::LeftBrace]) {
+            return self.map();
+        }
+
+        // while in expression position, its value comes from "break value;"
+        if self.match_tokens(&[TokenType::While]) {
+            let keyword = self.previous().clone();
+            let (condition, body) = self.while_clauses()?;
+            return Ok(Expr::loop_expr(keyword, condition, body));
+        }
+
+        // let x = 5 in x * x. The body reaches as far as it can: let x = 1 in x + 1 is let x = 1 in (x + 1).
+        if self.match_tokens(&[TokenType::Let]) {
+            let name = self.declared_name("Expect variable name after 'let'.")?;
+            self.consume(TokenType::Equal, "Expect '=' after let variable name.")?;
+            let value = self.expression()?;
+            self.consume(TokenType::In, "Expect 'in' after let value.")?;
+            let body = self.expression()?;
+            return Ok(Expr::let_expr(name, value, body));
+        }
+
+        Err(self.error_expecting(self.peek(), "Expect expression.", Self::EXPRESSION_START.to_vec()))
+    }
+
+    // Tokens that can begin an expression, listed when one is missing
+    const EXPRESSION_START: [TokenType; 13] = [
+        TokenType::Number,
+        TokenType::String,
+        TokenType::Identifier,
+        TokenType::True,
+        TokenType::False,
+        TokenType::Nil,
+        TokenType::LeftParen,
+        TokenType::LeftBracket,
+        TokenType::LeftBrace,
+        TokenType::Minus,
+        TokenType::Bang,
+        TokenType::While,
+        TokenType::Let,
+    ];
+
+    fn list(&mut self) -> Result<Expr> {
+        let mut elements = Vec::new();
+        if !self.check(&TokenType::RightBracket) {
+            loop {
+                elements.push(self.expression()?);
+                if !self.match_tokens(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+        self.consume_expecting(TokenType::RightBracket, &[TokenType::Comma], "Expect ']' after list elements.")?;
+        Ok(Expr::list(elements))
+    }
+
+    fn map(&mut self) -> Result<Expr> {
+        let brace = self.previous().clone();
+        let mut entries = Vec::new();
+        if !self.check(&TokenType::RightBrace) {
+            loop {
+                let key = self.expression()?;
+                self.consume(TokenType::Colon, "Expect ':' after map key.")?;
+                let value = self.expression()?;
+                entries.push((key, value));
+                if !self.match_tokens(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+        self.consume_expecting(TokenType::RightBrace, &[TokenType::Comma], "Expect '}' after map entries.")?;
+        Ok(Expr::map(brace, entries))
     }
 
     // Helper methods for token manipulation
@@ -483,18 +771,31 @@ impl Parser {
     fn check(&self, token_type: &TokenType) -> bool {
         // TODO: Return true if current token is of given type
         // Don't advance
-        if self.is_at_end() {return false;}
+        if self.is_at_end() {false}
         else {
             &self.peek().token_type == token_type
         }
     }
 
+    fn check_any(&self, token_types: &[TokenType]) -> bool {
+        token_types.iter().any(|token_type| self.check(token_type))
+    }
+
+    // Like check(), but for the token after the current one
+    fn check_next(&self, token_type: &TokenType) -> bool {
+        self.tokens.get(self.current + 1).is_some_and(|token| &token.token_type == token_type)
+    }
+
     fn advance(&mut self) -> &Token {
         // TODO: Move to next token and return the previous one
         if !self.is_at_end() { 
             self.current += 1;
         }
-        return self.previous();
+        self.previous()
+    }
+
+    fn only_closing_braces_left(&self) -> bool {
+        self.tokens[self.current..].iter().all(|token| matches!(token.token_type, TokenType::RightBrace | TokenType::Eof))
     }
 
     fn is_at_end(&self) -> bool {
@@ -509,32 +810,64 @@ impl Parser {
 
     fn previous(&self) -> &Token {
         // TODO: Return the previous token
-        return &self.tokens[self.current-1];
+        &self.tokens[self.current-1]
     }
 
     fn consume(&mut self, token_type: TokenType, message: &str) -> Result<&Token> {
+        self.consume_expecting(token_type, &[], message)
+    }
+
+    // Like consume(), but `alternatives` could also have come next (e.g. ',' before a closing ')'),
+    // so a failure can list everything that would have been valid
+    fn consume_expecting(&mut self, token_type: TokenType, alternatives: &[TokenType], message: &str) -> Result<&Token> {
         if self.check(&token_type) {
             Ok(self.advance())
         } else {
-            Err(self.error(self.peek(), message))  // NEW: Use error() method
+            let mut expected = alternatives.to_vec();
+            expected.push(token_type);
+            Err(self.error_expecting(self.peek(), message, expected))
         }
     }
 
+    // The name in a declaration: a variable, function, parameter, loop or catch variable, module alias, ...
+    fn declared_name(&mut self, message: &str) -> Result<Token> {
+        let name = self.consume(TokenType::Identifier, message)?.clone();
+        if name.lexeme == "Infinity" || name.lexeme == "NaN" {
+            return Err(self.error(&name, &format!("Can't use '{}' as a name, it's a number.", name.lexeme)));
+        }
+        Ok(name)
+    }
+
     fn error(&self, token: &Token, message: &str) -> anyhow::Error {
-        let error_msg = if token.token_type == TokenType::Eof {
+        self.error_expecting(token, message, Vec::new())
+    }
+
+    fn error_expecting(&self, token: &Token, message: &str, expected: Vec<TokenType>) -> anyhow::Error {
+        let mut error_msg = if token.token_type == TokenType::Eof {
             format!("{} at end", message)
         } else {
             format!("{} at '{}'", message, token.lexeme)
         };
+        // A single expected token is already named by the message itself
+        if expected.len() > 1 {
+            error_msg.push_str(&format!(" (expected {})", describe_expected(&expected)));
+        }
 
         ParseError {
             message: error_msg,
             line: token.line,
+            start: token.start,
+            length: token.lexeme.len(),
+            expected,
         }.into()
     }
 
-    fn synchronize(&mut self) {
-        self.advance();
+    // Skip ahead to where the next statement starts. The failed statement's first token is only skipped
+    // when nothing was consumed, so "var a = 1 var b = c;" resumes right at the second var instead of after it.
+    fn synchronize(&mut self, statement_start: usize) {
+        if self.current == statement_start {
+            self.advance();
+        }
 
         while !self.is_at_end() {
             if self.previous().token_type == TokenType::Semicolon {
@@ -549,6 +882,9 @@ impl Parser {
                 | TokenType::If
                 | TokenType::While
                 | TokenType::Print
+                | TokenType::Import
+                | TokenType::Try
+                | TokenType::Throw
                 | TokenType::Return => return,
                 _ => {}
             }
@@ -556,4 +892,14 @@ impl Parser {
             self.advance();
         }
     }
-}
\ No newline at end of file
+}
+
+// "',' or ')'" and "number, string or identifier"
+fn describe_expected(expected: &[TokenType]) -> String {
+    let names: Vec<&str> = expected.iter().map(TokenType::describe).collect();
+    match names.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,390 @@
+/*
+A pass over the parsed program that runs before the interpreter, looking at where each local variable
+is declared and used. Mostly it produces warnings, like:
+
+  {
+      var unused = 1;   <- [line 2] Warning: Local variable 'unused' is never used.
+  }
+
+Globals are never reported, since another file or a later REPL line may read them.
+Names starting with '_' are never reported either, so "var _ = f();" is a way to say "on purpose".
+
+Declaring the same name twice in one local scope is an error, and then the program doesn't run:
+
+  {
+      var a = 1;
+      var a = 2;        <- [line 3] Error: Already a variable with this name in this scope. at 'a'
+  }
+
+Globals can be redeclared as often as you like, so a script (or REPL line) can run "var a" again.
+*/
+
+use crate::error::ErrorReporter;
+use crate::expr::{Expr, ExprVisitor};
+use crate::stmt::{Stmt, StmtVisitor};
+use crate::token::{LiteralValue, Token};
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct ResolverWarning {
+    pub message: String,
+    pub token: Token, // Where the variable was declared
+}
+
+#[derive(Debug)]
+pub struct ResolverError {
+    pub message: String,
+    pub token: Token, // The second declaration
+}
+
+struct Local {
+    token: Token,
+    used: bool,
+    parameter: bool,
+}
+
+pub struct Resolver {
+    scopes: Vec<HashMap<String, Local>>,     // Innermost scope last. Empty at the top level (globals).
+    warn_unused_parameters: bool,            // Off by default: callbacks often ignore some of their arguments
+    warnings: Vec<ResolverWarning>,          // Everything found by the last resolve(), in source order
+    errors: Vec<ResolverError>,              // Same, for problems that stop the program from running
+    pending_functions: Vec<PendingFunction>, // Local function bodies waiting for their scope to be fully declared
+}
+
+// A local function's body is resolved when the scope it was declared in ends, so it sees the whole scope.
+// That's what the interpreter does too: the body runs when it's called, and by then the later names exist.
+struct PendingFunction {
+    depth: usize, // scopes.len() where the function was declared
+    params: Vec<Token>,
+    body: Vec<Stmt>,
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        Self {
+            scopes: Vec::new(),
+            warn_unused_parameters: false,
+            warnings: Vec::new(),
+            errors: Vec::new(),
+            pending_functions: Vec::new(),
+        }
+    }
+
+    pub fn set_warn_unused_parameters(&mut self, warn: bool) {
+        self.warn_unused_parameters = warn;
+    }
+
+    // Check a whole program, printing each error and warning through the reporter. Warnings never count as errors.
+    pub fn resolve(&mut self, statements: &[Stmt], error_reporter: &mut ErrorReporter) {
+        self.warnings.clear();
+        self.errors.clear();
+        self.resolve_statements(statements);
+        for error in &self.errors {
+            let token = &error.token;
+            let message = format!("{} at '{}'", error.message, token.lexeme);
+            error_reporter.report_span(token.line, token.start, token.lexeme.len(), &message);
+        }
+        self.warnings.sort_by_key(|warning| warning.token.start);
+        for warning in &self.warnings {
+            error_reporter.warning(warning.token.line, &warning.message);
+        }
+    }
+
+    pub fn warnings(&self) -> &[ResolverWarning] {
+        &self.warnings
+    }
+
+    pub fn errors(&self) -> &[ResolverError] {
+        &self.errors
+    }
+
+    fn resolve_statements(&mut self, statements: &[Stmt]) {
+        for statement in statements {
+            statement.accept(self);
+        }
+    }
+
+    fn resolve_expr(&mut self, expr: &Expr) {
+        expr.accept(self);
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        // Functions declared in this scope can now see every name in it, like isEven calling a later isOdd
+        let depth = self.scopes.len();
+        let split = self.pending_functions.iter().position(|function| function.depth == depth);
+        if let Some(split) = split {
+            for function in self.pending_functions.split_off(split) {
+                self.resolve_function(&function.params, &function.body);
+            }
+        }
+
+        let Some(scope) = self.scopes.pop() else { return };
+        for (name, local) in scope {
+            if local.used || name.starts_with('_') || (local.parameter && !self.warn_unused_parameters) {
+                continue;
+            }
+            let kind = if local.parameter { "Parameter" } else { "Local variable" };
+            self.warnings.push(ResolverWarning {
+                message: format!("{} '{}' is never used.", kind, name),
+                token: local.token,
+            });
+        }
+    }
+
+    // Globals aren't tracked, so only a local scope can complain about a name declared twice
+    fn declare(&mut self, name: &Token, parameter: bool) {
+        if let Some(scope) = self.scopes.last_mut() {
+            if scope.contains_key(&name.lexeme) {
+                self.errors.push(ResolverError {
+                    message: "Already a variable with this name in this scope.".to_string(),
+                    token: name.clone(),
+                });
+                return;
+            }
+            scope.insert(name.lexeme.clone(), Local { token: name.clone(), used: false, parameter });
+        }
+    }
+
+    fn resolve_function(&mut self, params: &[Token], body: &[Stmt]) {
+        self.begin_scope();
+        for param in params {
+            self.declare(param, true);
+        }
+        self.resolve_statements(body);
+        self.end_scope();
+    }
+
+    // Mark the innermost variable with this name as used. Nothing to do if it's a global.
+    fn mark_used(&mut self, name: &Token) {
+        for scope in self.scopes.iter_mut().rev() {
+            if let Some(local) = scope.get_mut(&name.lexeme) {
+                local.used = true;
+                return;
+            }
+        }
+    }
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StmtVisitor<()> for Resolver {
+    fn visit_expression_stmt(&mut self, _stmt: &Stmt, expression: &Expr) {
+        self.resolve_expr(expression);
+    }
+
+    fn visit_print_stmt(&mut self, _stmt: &Stmt, expressions: &[Expr]) {
+        for expression in expressions {
+            self.resolve_expr(expression);
+        }
+    }
+
+    fn visit_var_stmt(&mut self, _stmt: &Stmt, name: &Token, initializer: &Option<Box<Expr>>) {
+        if let Some(initializer) = initializer {
+            self.resolve_expr(initializer);
+        }
+        self.declare(name, false);
+    }
+
+    fn visit_block_stmt(&mut self, _stmt: &Stmt, statements: Vec<Stmt>) {
+        self.begin_scope();
+        self.resolve_statements(&statements);
+        self.end_scope();
+    }
+
+    fn visit_if_stmt(&mut self, _stmt: &Stmt, condition: &Expr, then_branch: &Stmt, else_branch: &Option<Box<Stmt>>) {
+        self.resolve_expr(condition);
+        then_branch.accept(self);
+        if let Some(else_branch) = else_branch {
+            else_branch.accept(self);
+        }
+    }
+
+    fn visit_while_stmt(&mut self, _stmt: &Stmt, _keyword: &Token, condition: &Expr, body: &Stmt) {
+        self.resolve_expr(condition);
+        body.accept(self);
+    }
+
+    fn visit_for_stmt(&mut self, _stmt: &Stmt, _keyword: &Token, initializer: Option<&Stmt>, condition: Option<&Expr>,
+                      increment: Option<&Expr>, body: &Stmt) {
+        self.begin_scope();
+        if let Some(initializer) = initializer {
+            initializer.accept(self);
+        }
+        if let Some(condition) = condition {
+            self.resolve_expr(condition);
+        }
+        if let Some(increment) = increment {
+            self.resolve_expr(increment);
+        }
+        body.accept(self);
+        self.end_scope();
+    }
+
+    // Declared before the body, so a local function can call itself. A local function's body waits for
+    // the end of its scope (see PendingFunction), so it can call functions declared after it too.
+    fn visit_function_stmt(&mut self, _stmt: &Stmt, name: &Token, params: &[Token], body: &[Stmt]) {
+        self.declare(name, false);
+        if self.scopes.is_empty() {
+            self.resolve_function(params, body);
+        } else {
+            let depth = self.scopes.len();
+            self.pending_functions.push(PendingFunction { depth, params: params.to_vec(), body: body.to_vec() });
+        }
+    }
+
+    fn visit_return_stmt(&mut self, _stmt: &Stmt, _keyword: &Token, value: &Option<Box<Expr>>) {
+        if let Some(value) = value {
+            self.resolve_expr(value);
+        }
+    }
+
+    fn visit_break_stmt(&mut self, _stmt: &Stmt, _keyword: &Token, value: &Option<Box<Expr>>) {
+        if let Some(value) = value {
+            self.resolve_expr(value);
+        }
+    }
+
+    fn visit_continue_stmt(&mut self, _stmt: &Stmt, _keyword: &Token) {}
+
+    fn visit_empty_stmt(&mut self, _stmt: &Stmt) {}
+
+    // An import defines names we can't see from here, so it never declares locals
+    fn visit_import_stmt(&mut self, _stmt: &Stmt, _keyword: &Token, _path: &Token, alias: &Option<Token>) {
+        if let Some(alias) = alias {
+            self.declare(alias, false);
+        }
+    }
+
+    fn visit_for_in_stmt(&mut self, _stmt: &Stmt, name: &Token, iterable: &Expr, body: &Stmt) {
+        self.resolve_expr(iterable);
+        self.begin_scope();
+        self.declare(name, false);
+        body.accept(self);
+        self.end_scope();
+    }
+
+    fn visit_destructure_stmt(&mut self, _stmt: &Stmt, _bracket: &Token, names: &[Token], rest: &Option<Token>, initializer: &Expr) {
+        self.resolve_expr(initializer);
+        for name in names.iter().chain(rest) {
+            self.declare(name, false);
+        }
+    }
+
+    fn visit_try_stmt(&mut self, _stmt: &Stmt, body: &[Stmt], name: &Token, handler: &[Stmt]) {
+        self.begin_scope();
+        self.resolve_statements(body);
+        self.end_scope();
+
+        // Like a parameter, the error doesn't have to be looked at
+        self.begin_scope();
+        self.declare(name, true);
+        self.resolve_statements(handler);
+        self.end_scope();
+    }
+
+    fn visit_throw_stmt(&mut self, _stmt: &Stmt, _keyword: &Token, value: &Expr) {
+        self.resolve_expr(value);
+    }
+}
+
+impl ExprVisitor<()> for Resolver {
+    fn visit_binary_expr(&mut self, _expr: &Expr, left: &Expr, _operator: &Token, right: &Expr) {
+        self.resolve_expr(left);
+        self.resolve_expr(right);
+    }
+
+    fn visit_unary_expr(&mut self, _expr: &Expr, _operator: &Token, right: &Expr) {
+        self.resolve_expr(right);
+    }
+
+    fn visit_literal_expr(&mut self, _expr: &Expr, _value: &Option<LiteralValue>) {}
+
+    fn visit_grouping_expr(&mut self, _expr: &Expr, expression: &Expr) {
+        self.resolve_expr(expression);
+    }
+
+    fn visit_variable_expr(&mut self, _expr: &Expr, name: &Token) {
+        self.mark_used(name);
+    }
+
+    fn visit_assign_expr(&mut self, _expr: &Expr, name: &Token, value: &Expr) {
+        self.resolve_expr(value);
+        self.mark_used(name);
+    }
+
+    fn visit_logical_expr(&mut self, _expr: &Expr, left: &Expr, _operator: &Token, right: &Expr) {
+        self.resolve_expr(left);
+        self.resolve_expr(right);
+    }
+
+    fn visit_call_expr(&mut self, _expr: &Expr, callee: &Expr, _paren: &Token, arguments: &[Expr]) {
+        self.resolve_expr(callee);
+        for argument in arguments {
+            self.resolve_expr(argument);
+        }
+    }
+
+    fn visit_loop_expr(&mut self, _expr: &Expr, _keyword: &Token, condition: &Expr, body: &Stmt) {
+        self.resolve_expr(condition);
+        body.accept(self);
+    }
+
+    fn visit_list_expr(&mut self, _expr: &Expr, elements: &[Expr]) {
+        for element in elements {
+            self.resolve_expr(element);
+        }
+    }
+
+    fn visit_map_expr(&mut self, _expr: &Expr, _brace: &Token, entries: &[(Expr, Expr)]) {
+        for (key, value) in entries {
+            self.resolve_expr(key);
+            self.resolve_expr(value);
+        }
+    }
+
+    fn visit_index_expr(&mut self, _expr: &Expr, object: &Expr, _bracket: &Token, index: &Expr) {
+        self.resolve_expr(object);
+        self.resolve_expr(index);
+    }
+
+    fn visit_index_set_expr(&mut self, _expr: &Expr, object: &Expr, _bracket: &Token, index: &Expr, value: &Expr) {
+        self.resolve_expr(object);
+        self.resolve_expr(index);
+        self.resolve_expr(value);
+    }
+
+    fn visit_get_expr(&mut self, _expr: &Expr, object: &Expr, _name: &Token) {
+        self.resolve_expr(object);
+    }
+
+    fn visit_set_expr(&mut self, _expr: &Expr, object: &Expr, _name: &Token, value: &Expr) {
+        self.resolve_expr(object);
+        self.resolve_expr(value);
+    }
+
+    fn visit_range_expr(&mut self, _expr: &Expr, start: &Expr, _operator: &Token, end: &Expr) {
+        self.resolve_expr(start);
+        self.resolve_expr(end);
+    }
+
+    // The name is a local of its own little scope, so an unused one is reported like any other
+    fn visit_let_expr(&mut self, _expr: &Expr, name: &Token, value: &Expr, body: &Expr) {
+        self.resolve_expr(value);
+        self.begin_scope();
+        self.declare(name, false);
+        self.resolve_expr(body);
+        self.end_scope();
+    }
+
+    fn visit_optional_get_expr(&mut self, _expr: &Expr, object: &Expr, _name: &Token) {
+        self.resolve_expr(object);
+    }
+}
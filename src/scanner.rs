@@ -5,77 +5,135 @@ Input: Raw source code string ("var x = 42;")
 Output: Stream of tokens ([VAR, IDENTIFIER("x"), EQUAL, NUMBER(42), SEMICOLON])
 Breaks text into meaningful chunks
 */
-use crate::token::{LiteralValue, Token, TokenType};
+use crate::token::{LiteralValue, Source, Token, TokenType};
 use std::collections::HashMap;
 use anyhow::{Result, anyhow};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanError {
+    pub message: String,
+    pub line: usize,
+    pub start: usize, // Byte offset where the bad token starts
+    pub column: usize, // Column of that offset, see Token::column
+    pub length: usize, // Bytes in the character at start, so a span over it stays on char boundaries
+}
+
+impl std::fmt::Display for ScanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[line {}] Error: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ScanError {}
+
 pub struct Scanner {
-    source: String,
+    source: Source,
     tokens: Vec<Token>,
+    errors: Vec<ScanError>, // Every bad token scan_tokens() skipped over
+    pending: Option<Token>, // The token scan_token() just produced, handed out by next()
+    emitted_eof: bool,      // The iterator ends after the Eof token
     start: usize,
     current: usize,
     line: usize,
+    tab_width: usize, // A tab moves the column to the next multiple of this, like in an editor
     keywords: HashMap<String, TokenType>,
 }
 
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
 impl Scanner {
     pub fn new(source: String) -> Self {
         let mut keywords = HashMap::new();
         keywords.insert("and".to_string(), TokenType::And);
+        keywords.insert("as".to_string(), TokenType::As);
+        keywords.insert("break".to_string(), TokenType::Break);
+        keywords.insert("catch".to_string(), TokenType::Catch);
         keywords.insert("class".to_string(), TokenType::Class);
+        keywords.insert("continue".to_string(), TokenType::Continue);
         keywords.insert("else".to_string(), TokenType::Else);
         keywords.insert("false".to_string(), TokenType::False);
         keywords.insert("for".to_string(), TokenType::For);
         keywords.insert("fun".to_string(), TokenType::Fun);
         keywords.insert("if".to_string(), TokenType::If);
+        keywords.insert("import".to_string(), TokenType::Import);
+        keywords.insert("in".to_string(), TokenType::In);
+        keywords.insert("let".to_string(), TokenType::Let);
         keywords.insert("nil".to_string(), TokenType::Nil);
         keywords.insert("or".to_string(), TokenType::Or);
         keywords.insert("print".to_string(), TokenType::Print);
         keywords.insert("return".to_string(), TokenType::Return);
         keywords.insert("super".to_string(), TokenType::Super);
         keywords.insert("this".to_string(), TokenType::This);
+        keywords.insert("throw".to_string(), TokenType::Throw);
         keywords.insert("true".to_string(), TokenType::True);
+        keywords.insert("try".to_string(), TokenType::Try);
         keywords.insert("var".to_string(), TokenType::Var);
         keywords.insert("while".to_string(), TokenType::While);
 
         Self {
-            source,
+            source: Source::new(source),
             tokens: Vec::new(),
+            errors: Vec::new(),
+            pending: None,
+            emitted_eof: false,
             start: 0,
             current: 0,
             line: 1,
+            tab_width: DEFAULT_TAB_WIDTH,
             keywords,
         }
     }
 
-    pub fn scan_tokens(&mut self) -> Result<&Vec<Token>> {
-        let mut errors = Vec::new();
+    // How many columns a tab stands for, so token columns match what the user's editor shows. 0 is treated as 1.
+    pub fn set_tab_width(&mut self, tab_width: usize) {
+        self.tab_width = tab_width.max(1);
+    }
 
-        while !self.is_at_end() {
-            self.start = self.current;
-            if let Err(error) = self.scan_token() {
-                errors.push(error.to_string());
-            }
+    // The 1-based column of a byte offset. Counts characters, not bytes, and a tab jumps to the next tab stop:
+    // with a width of 4, "\tx" puts x in column 5 and "ab\tx" does too.
+    pub fn column_of(&self, offset: usize) -> usize {
+        let line_start = self.source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let mut column = 0;
+        for c in self.source[line_start..offset].chars() {
+            column = if c == '\t' { (column / self.tab_width + 1) * self.tab_width } else { column + 1 };
         }
+        column + 1
+    }
 
-        self.tokens.push(Token::new(
-            TokenType::Eof,
-            "".to_string(),
-            None,
-            self.line,
-        ));
+    // Scan everything at once. Errors don't stop the scan: the bad character (or string, or number) is skipped
+    // and scanning carries on, so all errors are reported together. Even when this fails, tokens() has
+    // everything that did scan, so the parser can still look for errors further down.
+    // Use the scanner as an Iterator instead to get tokens one at a time.
+    pub fn scan_tokens(&mut self) -> Result<&Vec<Token>> {
+        while let Some(result) = self.next() {
+            match result {
+                Ok(token) => self.tokens.push(token),
+                Err(error) => self.errors.push(error),
+            }
+        }
 
-        if errors.is_empty() {
+        if self.errors.is_empty() {
             Ok(&self.tokens)
         } else {
-            Err(anyhow!(errors.join("\n")))
+            let messages: Vec<String> = self.errors.iter().map(ScanError::to_string).collect();
+            Err(anyhow!(messages.join("\n")))
         }
     }
 
+    // The tokens scan_tokens() produced, without the ones that had errors
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    pub fn errors(&self) -> &[ScanError] {
+        &self.errors
+    }
+
     fn is_at_end(&self) -> bool {
         self.current >= self.source.len()
     }
 
-    fn scan_token(&mut self) -> Result<()> {
+    fn scan_token(&mut self) -> Result<(), ScanError> {
         let c = self.advance();
         
         match c {
@@ -83,12 +141,47 @@ impl Scanner {
             ')' => self.add_token(TokenType::RightParen, None),
             '{' => self.add_token(TokenType::LeftBrace, None),
             '}' => self.add_token(TokenType::RightBrace, None),
+            '[' => self.add_token(TokenType::LeftBracket, None),
+            ']' => self.add_token(TokenType::RightBracket, None),
+            ':' => self.add_token(TokenType::Colon, None),
+            // The longest token wins, so "a?.b" is a ?. access. A lone '?' is for a ? b : c.
+            '?' => {
+                let token_type = if self.match_char('.') {
+                    TokenType::QuestionDot
+                } else {
+                    TokenType::Question
+                };
+                self.add_token(token_type, None);
+            }
+            // && and || are the same tokens as "and" and "or", only the lexeme differs. A single & or | is an error.
+            '&' if self.match_char('&') => self.add_token(TokenType::And, None),
+            '|' if self.match_char('|') => self.add_token(TokenType::Or, None),
             ',' => self.add_token(TokenType::Comma, None),
-            '.' => self.add_token(TokenType::Dot, None),
+            '.' => {
+                let token_type = if self.match_char('.') {
+                    if self.match_char('=') {
+                        TokenType::DotDotEqual
+                    } else if self.match_char('.') {
+                        TokenType::DotDotDot
+                    } else {
+                        TokenType::DotDot
+                    }
+                } else {
+                    TokenType::Dot
+                };
+                self.add_token(token_type, None);
+            }
             '-' => self.add_token(TokenType::Minus, None),
             '+' => self.add_token(TokenType::Plus, None),
             ';' => self.add_token(TokenType::Semicolon, None),
-            '*' => self.add_token(TokenType::Star, None),
+            '*' => {
+                let token_type = if self.match_char('*') {
+                    TokenType::StarStar
+                } else {
+                    TokenType::Star
+                };
+                self.add_token(token_type, None);
+            }
             '!' => {
                 let token_type = if self.match_char('=') {
                     TokenType::BangEqual
@@ -131,6 +224,13 @@ impl Scanner {
                     self.add_token(TokenType::Slash, None);
                 }
             }
+            // "#!/usr/bin/env lox" on the very first line, so scripts can be run directly.
+            // Anywhere else '#' is still an unexpected character.
+            '#' if self.start == 0 && self.match_char('!') => {
+                while self.peek() != '\n' && !self.is_at_end() {
+                    self.advance();
+                }
+            }
             ' ' | '\r' | '\t' => {
                 // Ignore whitespace
             }
@@ -138,13 +238,18 @@ impl Scanner {
                 self.line += 1;
             }
             '"' => return self.string(),
+            // r"C:\path" is a raw string: backslashes are kept as they are
+            'r' if self.peek() == '"' => {
+                self.advance();
+                return self.raw_string();
+            }
             _ => {
                 if self.is_digit(c) {
                     return self.number();
                 } else if self.is_alpha(c) {
                     self.identifier();
                 } else {
-                    return Err(anyhow!("[line {}] Error: Unexpected character '{}'", self.line, c));
+                    return Err(self.error(format!("Unexpected character '{}'", c)));
                 }
             }
         }
@@ -152,17 +257,19 @@ impl Scanner {
         Ok(())
     }
 
+    // start/current are byte offsets, so step over whole UTF-8 characters
+    // to keep the lexeme slices on char boundaries
     fn advance(&mut self) -> char {
-        let c = self.source.chars().nth(self.current).unwrap_or('\0');
-        self.current += 1;
+        let c = self.source[self.current..].chars().next().unwrap_or('\0');
+        self.current += c.len_utf8();
         c
     }
 
     fn match_char(&mut self, expected: char) -> bool {
-        if self.is_at_end() || self.source.chars().nth(self.current) != Some(expected) {
+        if self.is_at_end() || self.peek() != expected {
             return false;
         }
-        self.current += 1;
+        self.current += expected.len_utf8();
         true
     }
 
@@ -170,20 +277,45 @@ impl Scanner {
         if self.is_at_end() {
             '\0'
         } else {
-            self.source.chars().nth(self.current).unwrap_or('\0')
+            self.source[self.current..].chars().next().unwrap_or('\0')
         }
     }
 
     fn peek_next(&self) -> char {
-        if self.current + 1 >= self.source.len() {
+        if self.is_at_end() {
             '\0'
         } else {
-            self.source.chars().nth(self.current + 1).unwrap_or('\0')
+            self.source[self.current..].chars().nth(1).unwrap_or('\0')
         }
     }
 
-    fn string(&mut self) -> Result<()> {
+    fn string(&mut self) -> Result<(), ScanError> {
+        self.scan_to_closing_quote(true)?;
+
+        // Trim the surrounding quotes
+        let value = self.unescape(&self.source[self.start + 1..self.current - 1])?;
+        self.add_token(TokenType::String, Some(LiteralValue::String(value)));
+        Ok(())
+    }
+
+    fn raw_string(&mut self) -> Result<(), ScanError> {
+        self.scan_to_closing_quote(false)?;
+
+        // Trim the r" and the closing quote
+        let value = self.source[self.start + 2..self.current - 1].to_string();
+        self.add_token(TokenType::String, Some(LiteralValue::String(value)));
+        Ok(())
+    }
+
+    // Moves past the closing quote. With escapes on, \" doesn't end the string.
+    fn scan_to_closing_quote(&mut self, escapes: bool) -> Result<(), ScanError> {
         while self.peek() != '"' && !self.is_at_end() {
+            if escapes && self.peek() == '\\' {
+                self.advance();
+                if self.is_at_end() {
+                    break;
+                }
+            }
             if self.peek() == '\n' {
                 self.line += 1;
             }
@@ -191,27 +323,68 @@ impl Scanner {
         }
 
         if self.is_at_end() {
-            return Err(anyhow!("[line {}] Error: Unterminated string", self.line));
+            return Err(self.error("Unterminated string"));
         }
 
         // The closing "
         self.advance();
-
-        // Trim the surrounding quotes
-        let value = self.source[self.start + 1..self.current - 1].to_string();
-        self.add_token(TokenType::String, Some(LiteralValue::String(value)));
         Ok(())
     }
 
-    fn number(&mut self) -> Result<()> {
+    // Turns \n, \t, \r, \" and \\ into the characters they stand for.
+    // A backslash at the end of a line joins the lines: "foo\<newline>bar" is "foobar".
+    fn unescape(&self, text: &str) -> Result<String, ScanError> {
+        let mut result = String::with_capacity(text.len());
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                result.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('t') => result.push('\t'),
+                Some('r') => result.push('\r'),
+                Some('"') => result.push('"'),
+                Some('\\') => result.push('\\'),
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next(); // A Windows line ending
+                }
+                Some(other) => {
+                    return Err(self.error(format!("Unknown escape sequence '\\{}' in string", other)));
+                }
+                None => return Err(self.error("Unterminated string")),
+            }
+        }
+        Ok(result)
+    }
+
+    fn number(&mut self) -> Result<(), ScanError> {
+        // 0xFF, 0o17 and 0b1010
+        if &self.source[self.start..self.current] == "0" {
+            let radix = match self.peek() {
+                'x' | 'X' => Some((16, "hexadecimal")),
+                'o' | 'O' => Some((8, "octal")),
+                'b' | 'B' => Some((2, "binary")),
+                _ => None,
+            };
+            if let Some((radix, name)) = radix {
+                self.advance();
+                return self.radix_number(radix, name);
+            }
+        }
+
         while self.is_digit(self.peek()) {
             self.advance();
         }
 
         // Look for a fractional part
+        let mut is_float = false;
         if self.peek() == '.' && self.is_digit(self.peek_next()) {
             // Consume the "."
             self.advance();
+            is_float = true;
 
             while self.is_digit(self.peek()) {
                 self.advance();
@@ -219,13 +392,46 @@ impl Scanner {
         }
 
         let value_str = &self.source[self.start..self.current];
+
+        // No decimal point means an integer, unless it is too big for i64
+        if !is_float && let Ok(value) = value_str.parse::<i64>() {
+            self.add_token(TokenType::Number, Some(LiteralValue::Int(value)));
+            return Ok(());
+        }
+
         match value_str.parse::<f64>() {
             Ok(value) => {
                 self.add_token(TokenType::Number, Some(LiteralValue::Number(value)));
                 Ok(())
             }
-            Err(_) => Err(anyhow!("[line {}] Error: Invalid number format", self.line)),
+            Err(_) => Err(self.error("Invalid number format")),
+        }
+    }
+
+    fn radix_number(&mut self, radix: u32, name: &str) -> Result<(), ScanError> {
+        // Take every letter and digit, so a typo like 0xG1 is one bad literal and not "0x" followed by "G1"
+        while self.is_alphanumeric(self.peek()) {
+            self.advance();
+        }
+
+        let lexeme = &self.source[self.start..self.current];
+        let digits = &lexeme[2..];
+        if digits.is_empty() {
+            return Err(self.error(format!("Expect digits after '{}'", lexeme)));
+        }
+        if let Some(bad) = digits.chars().find(|c| !c.is_digit(radix)) {
+            return Err(self.error(format!("Invalid digit '{}' in {} literal '{}'", bad, name, lexeme)));
         }
+
+        // Like decimal literals, fall back to a float when it doesn't fit in an i64
+        let literal = match i64::from_str_radix(digits, radix) {
+            Ok(value) => LiteralValue::Int(value),
+            Err(_) => LiteralValue::Number(
+                digits.chars().fold(0.0, |acc, c| acc * radix as f64 + c.to_digit(radix).unwrap() as f64),
+            ),
+        };
+        self.add_token(TokenType::Number, Some(literal));
+        Ok(())
     }
 
     fn identifier(&mut self) {
@@ -261,6 +467,48 @@ impl Scanner {
 
     fn add_token(&mut self, token_type: TokenType, literal: Option<LiteralValue>) {
         let text = self.source[self.start..self.current].to_string();
-        self.tokens.push(Token::new(token_type, text, literal, self.line));
+        let mut token = Token::new_at(token_type, text, literal, self.line, self.start);
+        token.column = self.column_of(self.start);
+        token.source = Some(self.source.clone());
+        self.pending = Some(token);
+    }
+
+    fn error(&self, message: impl Into<String>) -> ScanError {
+        ScanError {
+            message: message.into(),
+            line: self.line,
+            start: self.start,
+            column: self.column_of(self.start),
+            length: self.source[self.start..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+}
+
+// Tokens one at a time, ending with Eof. A bad token gives an Err and scanning carries on after it.
+impl Iterator for Scanner {
+    type Item = Result<Token, ScanError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.is_at_end() {
+                if self.emitted_eof {
+                    return None;
+                }
+                self.emitted_eof = true;
+                let mut eof = Token::new_at(TokenType::Eof, "".to_string(), None, self.line, self.current);
+                eof.column = self.column_of(self.current);
+                eof.source = Some(self.source.clone());
+                return Some(Ok(eof));
+            }
+
+            // Whitespace and comments produce no token, so keep going until something does
+            self.start = self.current;
+            if let Err(error) = self.scan_token() {
+                return Some(Err(error));
+            }
+            if let Some(token) = self.pending.take() {
+                return Some(Ok(token));
+            }
+        }
     }
-}
\ No newline at end of file
+}
@@ -6,8 +6,8 @@ pub enum Stmt {
     Expression { // Ex. 1+2;
         expression: Box<Expr>,
     },
-    Print {
-        expression: Box<Expr>,
+    Print { // print a, b; prints the values separated by spaces
+        expressions: Vec<Expr>,
     },
     Var {
         name: Token,
@@ -22,9 +22,17 @@ pub enum Stmt {
         else_branch: Option<Box<Stmt>>
     },
     While {
+        keyword: Token, // The "while" token, for error reporting
         condition: Box<Expr>,
         body: Box<Stmt>
     },
+    For { // for (var i = 0; i < 3; i = i + 1) body. Each iteration gets its own copy of i, see visit_for_stmt.
+        keyword: Token,
+        initializer: Option<Box<Stmt>>,
+        condition: Option<Box<Expr>>, // None loops forever
+        increment: Option<Box<Expr>>,
+        body: Box<Stmt>,
+    },
     /*
     fun add(a, b) {    // <-- This creates a Stmt::Function
         return a + b;
@@ -38,6 +46,39 @@ pub enum Stmt {
     Return {
         keyword: Token,
         value: Option<Box<Expr>>,
+    },
+    Break { // break; or break value; to give a while expression its value
+        keyword: Token,
+        value: Option<Box<Expr>>,
+    },
+    Empty, // A lone ;, as in "for (;;) ;" or a doubled ";;". Does nothing.
+    Continue { // continue; skips to the next iteration (a for loop still runs its increment)
+        keyword: Token,
+    },
+    Import { // import "helpers.lox"; or import "math.lox" as m;
+        keyword: Token,
+        path: Token, // The string token naming the file
+        alias: Option<Token>, // With "as", the file becomes a module under this name
+    },
+    ForIn { // for (i in 1..10) print i;
+        name: Token,
+        iterable: Box<Expr>,
+        body: Box<Stmt>,
+    },
+    Destructure { // var [a, b, ...rest] = list;
+        bracket: Token, // The '[' of the pattern, for error reporting
+        names: Vec<Token>,
+        rest: Option<Token>, // Gets a list of whatever is left after the names
+        initializer: Box<Expr>,
+    },
+    Try { // try { ... } catch (e) { ... }
+        body: Vec<Stmt>,
+        name: Token, // Holds the error message inside the catch block
+        handler: Vec<Stmt>,
+    },
+    Throw { // throw "message";
+        keyword: Token,
+        value: Box<Expr>,
     }
 }
 /*
@@ -56,9 +97,9 @@ let stmt = Stmt::Expression {
 // This creates a Print Statement
 // print "hello";
 let stmt = Stmt::Print {
-    expression: Box::new(
+    expressions: vec![
         Expr::literal(Some(LiteralValue::String("hello".to_string())))
-    ),
+    ],
 };
 
 // This creates a Var
@@ -80,13 +121,23 @@ let stmt = Stmt::Var {
 // Visitor pattern for statements
 pub trait StmtVisitor<T> {
     fn visit_expression_stmt(&mut self, stmt: &Stmt, expression: &Expr) -> T;
-    fn visit_print_stmt(&mut self, stmt: &Stmt, expression: &Expr) -> T;
+    fn visit_print_stmt(&mut self, stmt: &Stmt, expressions: &[Expr]) -> T;
     fn visit_var_stmt(&mut self, stmt: &Stmt, name: &Token, initializer: &Option<Box<Expr>>) -> T;
     fn visit_block_stmt(&mut self, stmt: &Stmt, statements: Vec<Stmt>) -> T;
     fn visit_if_stmt(&mut self, stmt: &Stmt, condition: &Expr, then_branch: &Stmt, else_branch: &Option<Box<Stmt>>) -> T;
-    fn visit_while_stmt(&mut self, stmt: &Stmt, condition: &Expr, body: &Stmt) -> T;
+    fn visit_while_stmt(&mut self, stmt: &Stmt, keyword: &Token, condition: &Expr, body: &Stmt) -> T;
+    fn visit_for_stmt(&mut self, stmt: &Stmt, keyword: &Token, initializer: Option<&Stmt>, condition: Option<&Expr>,
+                      increment: Option<&Expr>, body: &Stmt) -> T;
     fn visit_function_stmt(&mut self, stmt: &Stmt, name: &Token, params: &[Token], body: &[Stmt]) -> T;
     fn visit_return_stmt(&mut self, stmt: &Stmt, keyword: &Token, value: &Option<Box<Expr>>) -> T;
+    fn visit_break_stmt(&mut self, stmt: &Stmt, keyword: &Token, value: &Option<Box<Expr>>) -> T;
+    fn visit_continue_stmt(&mut self, stmt: &Stmt, keyword: &Token) -> T;
+    fn visit_empty_stmt(&mut self, stmt: &Stmt) -> T;
+    fn visit_import_stmt(&mut self, stmt: &Stmt, keyword: &Token, path: &Token, alias: &Option<Token>) -> T;
+    fn visit_for_in_stmt(&mut self, stmt: &Stmt, name: &Token, iterable: &Expr, body: &Stmt) -> T;
+    fn visit_destructure_stmt(&mut self, stmt: &Stmt, bracket: &Token, names: &[Token], rest: &Option<Token>, initializer: &Expr) -> T;
+    fn visit_try_stmt(&mut self, stmt: &Stmt, body: &[Stmt], name: &Token, handler: &[Stmt]) -> T;
+    fn visit_throw_stmt(&mut self, stmt: &Stmt, keyword: &Token, value: &Expr) -> T;
 }
 // Visitor Pattern
 // Calling accept(...) in the interpreter means executing statements
@@ -98,8 +149,8 @@ impl Stmt {
             Stmt::Expression { expression } => {
                 visitor.visit_expression_stmt(self, expression)
             },
-            Stmt::Print { expression } => {
-                visitor.visit_print_stmt(self, expression)
+            Stmt::Print { expressions } => {
+                visitor.visit_print_stmt(self, expressions)
             },
             Stmt::Var { name, initializer } => {
                 visitor.visit_var_stmt(self, name, initializer)
@@ -110,8 +161,11 @@ impl Stmt {
             Stmt::If { condition, then_branch, else_branch } => {
                 visitor.visit_if_stmt(self, condition, then_branch, else_branch)
             }
-            Stmt::While { condition, body } => {
-                visitor.visit_while_stmt(self, condition, body)
+            Stmt::While { keyword, condition, body } => {
+                visitor.visit_while_stmt(self, keyword, condition, body)
+            }
+            Stmt::For { keyword, initializer, condition, increment, body } => {
+                visitor.visit_for_stmt(self, keyword, initializer.as_deref(), condition.as_deref(), increment.as_deref(), body)
             }
             Stmt::Function { name, params, body } => {
                 visitor.visit_function_stmt(self, name, params, body)
@@ -119,6 +173,28 @@ impl Stmt {
             Stmt::Return { keyword, value } => {
                 visitor.visit_return_stmt(self, keyword, value)
             }
+            Stmt::Break { keyword, value } => {
+                visitor.visit_break_stmt(self, keyword, value)
+            }
+            Stmt::Continue { keyword } => {
+                visitor.visit_continue_stmt(self, keyword)
+            }
+            Stmt::Empty => visitor.visit_empty_stmt(self),
+            Stmt::Import { keyword, path, alias } => {
+                visitor.visit_import_stmt(self, keyword, path, alias)
+            }
+            Stmt::ForIn { name, iterable, body } => {
+                visitor.visit_for_in_stmt(self, name, iterable, body)
+            }
+            Stmt::Destructure { bracket, names, rest, initializer } => {
+                visitor.visit_destructure_stmt(self, bracket, names, rest, initializer)
+            }
+            Stmt::Try { body, name, handler } => {
+                visitor.visit_try_stmt(self, body, name, handler)
+            }
+            Stmt::Throw { keyword, value } => {
+                visitor.visit_throw_stmt(self, keyword, value)
+            }
         }
     }
 
@@ -128,14 +204,14 @@ impl Stmt {
         Stmt::Expression { expression: Box::new(expr) }
     }
 
-    pub fn print(expr: Expr) -> Self {
+    pub fn print(expressions: Vec<Expr>) -> Self {
         // TODO: Create Print variant
-        Stmt::Print { expression: Box::new(expr) }
+        Stmt::Print { expressions }
     }
 
     pub fn var(name: Token, initializer: Option<Expr>) -> Self {
         // TODO: Create Var variant
-        Stmt::Var { name: name, initializer: initializer.map(Box::new) }
+        Stmt::Var { name, initializer: initializer.map(Box::new) }
     }
 
     pub fn block(statements: Vec<Stmt>) -> Self {
@@ -150,8 +226,19 @@ impl Stmt {
         }
     }
 
-    pub fn while_stmt(condition: Expr, body: Stmt) -> Self {
+    pub fn for_stmt(keyword: Token, initializer: Option<Stmt>, condition: Option<Expr>, increment: Option<Expr>, body: Stmt) -> Self {
+        Stmt::For {
+            keyword,
+            initializer: initializer.map(Box::new),
+            condition: condition.map(Box::new),
+            increment: increment.map(Box::new),
+            body: Box::new(body),
+        }
+    }
+
+    pub fn while_stmt(keyword: Token, condition: Expr, body: Stmt) -> Self {
         Stmt::While {
+            keyword,
             condition: Box::new(condition),
             body: Box::new(body),
         }
@@ -167,4 +254,35 @@ impl Stmt {
             value: value.map(Box::new),
         }
     }
-}
\ No newline at end of file
+
+    pub fn break_stmt(keyword: Token, value: Option<Expr>) -> Self {
+        Stmt::Break {
+            keyword,
+            value: value.map(Box::new),
+        }
+    }
+
+    pub fn continue_stmt(keyword: Token) -> Self {
+        Stmt::Continue { keyword }
+    }
+
+    pub fn import(keyword: Token, path: Token, alias: Option<Token>) -> Self {
+        Stmt::Import { keyword, path, alias }
+    }
+
+    pub fn for_in(name: Token, iterable: Expr, body: Stmt) -> Self {
+        Stmt::ForIn { name, iterable: Box::new(iterable), body: Box::new(body) }
+    }
+
+    pub fn try_stmt(body: Vec<Stmt>, name: Token, handler: Vec<Stmt>) -> Self {
+        Stmt::Try { body, name, handler }
+    }
+
+    pub fn throw(keyword: Token, value: Expr) -> Self {
+        Stmt::Throw { keyword, value: Box::new(value) }
+    }
+
+    pub fn destructure(bracket: Token, names: Vec<Token>, rest: Option<Token>, initializer: Expr) -> Self {
+        Stmt::Destructure { bracket, names, rest, initializer: Box::new(initializer) }
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenType {
     // Single-character tokens
@@ -5,10 +7,14 @@ pub enum TokenType {
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
+    Colon,
     Comma,
     Dot,
     Minus,
     Plus,
+    Question,
     Semicolon,
     Slash,
     Star,
@@ -22,6 +28,11 @@ pub enum TokenType {
     GreaterEqual,
     Less,
     LessEqual,
+    StarStar,    // ** exponent
+    DotDot,      // ..  exclusive range
+    DotDotEqual, // ..= inclusive range
+    DotDotDot,   // ... rest of a list pattern
+    QuestionDot, // ?. property access that gives nil on nil
 
     // Literals
     Identifier,
@@ -30,37 +41,143 @@ pub enum TokenType {
 
     // Keywords
     And,
+    As,
+    Break,
+    Catch,
     Class,
+    Continue,
     Else,
     False,
     Fun,
     For,
     If,
+    Import,
+    In,
+    Let,
     Nil,
     Or,
     Print,
     Return,
     Super,
     This,
+    Throw,
     True,
+    Try,
     Var,
     While,
 
     Eof,
 }
 
+impl TokenType {
+    // How the token is named in error messages: punctuation and keywords as written, the rest by kind
+    pub fn describe(&self) -> &'static str {
+        match self {
+            TokenType::LeftParen => "'('",
+            TokenType::RightParen => "')'",
+            TokenType::LeftBrace => "'{'",
+            TokenType::RightBrace => "'}'",
+            TokenType::LeftBracket => "'['",
+            TokenType::RightBracket => "']'",
+            TokenType::Colon => "':'",
+            TokenType::Question => "'?'",
+            TokenType::Comma => "','",
+            TokenType::Dot => "'.'",
+            TokenType::Minus => "'-'",
+            TokenType::Plus => "'+'",
+            TokenType::Semicolon => "';'",
+            TokenType::Slash => "'/'",
+            TokenType::Star => "'*'",
+            TokenType::Bang => "'!'",
+            TokenType::BangEqual => "'!='",
+            TokenType::Equal => "'='",
+            TokenType::EqualEqual => "'=='",
+            TokenType::Greater => "'>'",
+            TokenType::GreaterEqual => "'>='",
+            TokenType::Less => "'<'",
+            TokenType::LessEqual => "'<='",
+            TokenType::StarStar => "'**'",
+            TokenType::DotDot => "'..'",
+            TokenType::DotDotEqual => "'..='",
+            TokenType::DotDotDot => "'...'",
+            TokenType::QuestionDot => "'?.'",
+            TokenType::Identifier => "identifier",
+            TokenType::String => "string",
+            TokenType::Number => "number",
+            TokenType::And => "'and'",
+            TokenType::As => "'as'",
+            TokenType::Break => "'break'",
+            TokenType::Catch => "'catch'",
+            TokenType::Class => "'class'",
+            TokenType::Continue => "'continue'",
+            TokenType::Else => "'else'",
+            TokenType::False => "'false'",
+            TokenType::Fun => "'fun'",
+            TokenType::For => "'for'",
+            TokenType::If => "'if'",
+            TokenType::Import => "'import'",
+            TokenType::In => "'in'",
+            TokenType::Let => "'let'",
+            TokenType::Nil => "'nil'",
+            TokenType::Or => "'or'",
+            TokenType::Print => "'print'",
+            TokenType::Return => "'return'",
+            TokenType::Super => "'super'",
+            TokenType::This => "'this'",
+            TokenType::Throw => "'throw'",
+            TokenType::True => "'true'",
+            TokenType::Try => "'try'",
+            TokenType::Var => "'var'",
+            TokenType::While => "'while'",
+            TokenType::Eof => "end of file",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Token {
     pub token_type: TokenType,
     pub lexeme: String,
     pub literal: Option<LiteralValue>,
     pub line: usize,
+    pub start: usize, // Byte offset of the lexeme in the source, used to point at it in error messages
+    pub end: usize,   // Byte offset just past the lexeme, so source[start..end] is the lexeme
+    pub column: usize, // 1-based column of the first character as an editor shows it (tabs expanded), 0 if unknown
+    pub source: Option<Source>, // The code the token was scanned from, None for tokens made by hand
+}
+
+// The text a scanner ran over, shared by all of its tokens. A runtime error can come from an imported
+// file or an earlier REPL input, so the error's token says which code to show the line from.
+// Arc rather than Rc because a RuntimeError holds a token and anyhow errors must be Send.
+#[derive(Clone)]
+pub struct Source(Arc<str>);
+
+impl Source {
+    pub fn new(text: impl Into<Arc<str>>) -> Self {
+        Self(text.into())
+    }
+}
+
+impl std::ops::Deref for Source {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+// Tokens and statements get printed with {:?}, and a whole file per token would bury everything else
+impl std::fmt::Debug for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Source({} bytes)", self.0.len())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum LiteralValue {
     String(String),
     Number(f64),
+    Int(i64), // Number literals without a decimal point
     Boolean(bool),
     Nil,
 }
@@ -72,35 +189,76 @@ impl Token {
         literal: Option<LiteralValue>,
         line: usize,
     ) -> Self {
+        Self::new_at(token_type, lexeme, literal, line, 0)
+    }
+
+    // Same as new(), but also records where the token starts in the source. The scanner uses this.
+    // The lexeme is always the exact source text, so it tells us where the token ends too.
+    pub fn new_at(
+        token_type: TokenType,
+        lexeme: String,
+        literal: Option<LiteralValue>,
+        line: usize,
+        start: usize,
+    ) -> Self {
+        let end = start + lexeme.len();
         Self {
             token_type,
             lexeme,
             literal,
             line,
+            start,
+            end,
+            column: 0,
+            source: None,
         }
     }
+
+    // Same type, lexeme and literal, wherever each one is in the source. Handy in tests, where the expected
+    // tokens are made with Token::new() and have no position. Token has no PartialEq on purpose: it would
+    // have to pick between comparing positions or not, and either choice surprises someone.
+    pub fn same_kind(&self, other: &Token) -> bool {
+        self.token_type == other.token_type && self.lexeme == other.lexeme && self.literal == other.literal
+    }
 }
 
+// Number '42' (42), String '"hi"' ("hi"), Var 'var'
 impl std::fmt::Display for Token {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?} {} {:?}", self.token_type, self.lexeme, self.literal)
+        write!(f, "{:?} '{}'", self.token_type, self.lexeme)?;
+        if let Some(literal) = &self.literal {
+            write!(f, " ({})", literal)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for LiteralValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LiteralValue::String(s) => write!(f, "\"{}\"", s),
+            LiteralValue::Number(n) => write!(f, "{}", n),
+            LiteralValue::Int(n) => write!(f, "{}", n),
+            LiteralValue::Boolean(b) => write!(f, "{}", b),
+            LiteralValue::Nil => write!(f, "nil"),
+        }
     }
 }
 
 /*
 Input: var myVariable = 42;
 [
-    Token { token_type: TokenType::Var, lexeme: "var", literal: None, line: 1 },
-    Token { token_type: TokenType::Identifier, lexeme: "myVariable", literal: None, line: 1 },
-    Token { token_type: TokenType::Equal, lexeme: "=", literal: None, line: 1 },
-    Token { token_type: TokenType::Number, lexeme: "42", literal: Some(LiteralValue::Number(42.0)), line: 1 },
-    Token { token_type: TokenType::Semicolon, lexeme: ";", literal: None, line: 1 },
+    Token { token_type: TokenType::Var, lexeme: "var", literal: None, line: 1, start: 0, end: 3, column: 1 },
+    Token { token_type: TokenType::Identifier, lexeme: "myVariable", literal: None, line: 1, start: 4, end: 14, column: 5 },
+    Token { token_type: TokenType::Equal, lexeme: "=", literal: None, line: 1, start: 15, end: 16, column: 16 },
+    Token { token_type: TokenType::Number, lexeme: "42", literal: Some(LiteralValue::Int(42)), line: 1, start: 17, end: 19, column: 18 },
+    Token { token_type: TokenType::Semicolon, lexeme: ";", literal: None, line: 1, start: 19, end: 20, column: 20 },
 ]
 
 Input: print userName;
 [
-    Token { token_type: TokenType::Print, lexeme: "print", literal: None, line: 1 },
-    Token { token_type: TokenType::Identifier, lexeme: "userName", literal: None, line: 1 },
-    Token { token_type: TokenType::Semicolon, lexeme: ";", literal: None, line: 1 },
+    Token { token_type: TokenType::Print, lexeme: "print", literal: None, line: 1, start: 0, end: 5, column: 1 },
+    Token { token_type: TokenType::Identifier, lexeme: "userName", literal: None, line: 1, start: 6, end: 14, column: 7 },
+    Token { token_type: TokenType::Semicolon, lexeme: ";", literal: None, line: 1, start: 14, end: 15, column: 15 },
 ]
 */
\ No newline at end of file
@@ -1,15 +1,35 @@
-use std::fmt;
+use anyhow::anyhow;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
 use crate::function::LoxFunction;
-use crate::native::NativeFunction;
+use crate::native::{BoundNative, HostFunction, NativeFunction};
+use crate::environment::EnvId;
+
+// A file imported with "import ... as name". It ran in its own global environment,
+// and its top-level names are read from there with a dot: name.square(3)
+#[derive(Debug, Clone)]
+pub struct LoxModule {
+    pub name: String,
+    pub env: EnvId,
+}
 
 #[derive(Debug)]
 pub enum Value {
     Number(f64),
+    Int(i64),
     String(String),
     Boolean(bool),
     Nil,
     Function(LoxFunction),
     NativeFunction(NativeFunction),
+    BoundNative(Rc<BoundNative>), // A string method like "abc".upper, waiting to be called
+    HostFunction(Rc<HostFunction>), // Registered from Rust with Interpreter::define_native()
+    // Lists and maps are reference types: copies share the same storage, so a[0][1] = 5 mutates in place
+    List(Rc<RefCell<Vec<Value>>>),
+    Map(Rc<RefCell<BTreeMap<String, Value>>>), // Sorted by key so printing is deterministic
+    Module(Rc<LoxModule>),
+    Range { start: i64, end: i64, inclusive: bool }, // 1..3 is 1, 2 and 1..=3 is 1, 2, 3
 }
 
 impl Value {
@@ -25,38 +45,225 @@ impl Value {
     }
     
     pub fn is_equal(&self, other: &Value) -> bool {
-        // TODO: Implement equality comparison
-        // This is different from Rust's PartialEq - it's Lox's equality rules
+        self.is_equal_guarded(other, &mut Vec::new())
+    }
+
+    // Lists and maps compare by contents. `comparing` holds the pairs of lists/maps already being compared
+    // further up, so a list that contains itself doesn't recurse forever: meeting the same pair again counts as equal.
+    fn is_equal_guarded(&self, other: &Value, comparing: &mut Vec<(usize, usize)>) -> bool {
         match (self, other) {
             (Value::Nil, Value::Nil) => true,
             (Value::Number(a), Value::Number(b)) => a==b,
+            (Value::Int(a), Value::Int(b)) => a==b,
+            // 1 == 1.0 is true, ints and floats compare by numeric value
+            (Value::Int(a), Value::Number(b)) | (Value::Number(b), Value::Int(a)) => *a as f64 == *b,
             (Value::String(a), Value::String(b)) => a==b,
             (Value::Boolean(a), Value::Boolean(b)) => a==b,
             (Value::Function(a), Value::Function(b)) => a == b,
             (Value::NativeFunction(a), Value::NativeFunction(b)) => a.name() == b.name(),
+            (Value::HostFunction(a), Value::HostFunction(b)) => Rc::ptr_eq(a, b),
+            (Value::List(a), Value::List(b)) => {
+                let pair = (Rc::as_ptr(a) as usize, Rc::as_ptr(b) as usize);
+                if Rc::ptr_eq(a, b) || comparing.contains(&pair) {
+                    return true;
+                }
+                comparing.push(pair);
+                let (a, b) = (a.borrow(), b.borrow());
+                let equal = a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| x.is_equal_guarded(y, comparing));
+                comparing.pop();
+                equal
+            }
+            (Value::Map(a), Value::Map(b)) => {
+                let pair = (Rc::as_ptr(a) as usize, Rc::as_ptr(b) as usize);
+                if Rc::ptr_eq(a, b) || comparing.contains(&pair) {
+                    return true;
+                }
+                comparing.push(pair);
+                let (a, b) = (a.borrow(), b.borrow());
+                // Both maps are sorted by key, so walking them side by side lines the keys up
+                let equal = a.len() == b.len()
+                    && a.iter().zip(b.iter()).all(|((ka, va), (kb, vb))| ka == kb && va.is_equal_guarded(vb, comparing));
+                comparing.pop();
+                equal
+            }
+            (Value::Module(a), Value::Module(b)) => a.env == b.env,
+            (Value::Range { start: s1, end: e1, inclusive: i1 }, Value::Range { start: s2, end: e2, inclusive: i2 }) => {
+                s1 == s2 && e1 == e2 && i1 == i2
+            }
             _ => false,
         }
     }
+
+    pub fn list(elements: Vec<Value>) -> Self {
+        Value::List(Rc::new(RefCell::new(elements)))
+    }
+
+    pub fn map(entries: BTreeMap<String, Value>) -> Self {
+        Value::Map(Rc::new(RefCell::new(entries)))
+    }
+
+    // For Rust code reading values, e.g. in a host function. None when the value is of another kind.
+    // Ints count as numbers: Value::Int(2).as_number() is Some(2.0).
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            Value::Int(n) => Some(*n as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_string(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    // Only actual booleans, not truthiness: Value::Nil.as_bool() is None
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+impl From<f64> for Value {
+    fn from(n: f64) -> Self {
+        Value::Number(n)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(n: i64) -> Self {
+        Value::Int(n)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Boolean(b)
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+// The other way, failing when the value is of another kind: f64::try_from(value)?
+impl TryFrom<Value> for f64 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> anyhow::Result<Self> {
+        value.as_number().ok_or_else(|| anyhow!("Expected a number but got {}.", value))
+    }
+}
+
+impl TryFrom<Value> for bool {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> anyhow::Result<Self> {
+        value.as_bool().ok_or_else(|| anyhow!("Expected a boolean but got {}.", value))
+    }
+}
+
+impl TryFrom<Value> for String {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> anyhow::Result<Self> {
+        match value {
+            Value::String(s) => Ok(s),
+            other => Err(anyhow!("Expected a string but got {}.", other)),
+        }
+    }
 }
 
 impl std::fmt::Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // TODO: Implement display formatting for Lox values
-        // Numbers should print without trailing .0 if they're whole numbers
-        match self {
-            Value::Number(n) => {
-                if n.fract() == 0.0 {
-                    write!(f, "{}", *n as i64)
-                } else {
-                    write!(f, "{}", n)
+        WithPrecision(self, None).fmt(f)
+    }
+}
+
+// Displays a value with non-integer numbers rounded to a fixed number of decimals (None = shortest round-trip).
+// The interpreter uses this for "print" since Display alone can't see the interpreter's setting.
+pub struct WithPrecision<'a>(pub &'a Value, pub Option<usize>);
+
+impl std::fmt::Display for WithPrecision<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let WithPrecision(value, precision) = *self;
+        write_value(f, value, precision, &mut Vec::new())
+    }
+}
+
+// `printing` holds the lists and maps we're already inside of, so one that contains itself shows up as
+// [...] or {...} the second time instead of recursing forever. A list that only appears twice side by side,
+// like [b, b], isn't a cycle and prints in full both times.
+fn write_value(f: &mut std::fmt::Formatter<'_>, value: &Value, precision: Option<usize>, printing: &mut Vec<usize>) -> std::fmt::Result {
+    // Whole numbers print without a trailing .0. Rust's {} for f64 already does that, never uses an exponent,
+    // and keeps the sign of zero, so -0.0 prints as "-0" like in the reference Lox.
+    match value {
+        Value::Number(n) if n.is_nan() => write!(f, "NaN"),
+        Value::Number(n) if n.is_infinite() => write!(f, "{}Infinity", if *n < 0.0 { "-" } else { "" }),
+        Value::Number(n) => {
+            if n.fract() == 0.0 {
+                write!(f, "{}", n)
+            } else if let Some(precision) = precision {
+                write!(f, "{:.*}", precision, n)
+            } else {
+                write!(f, "{}", n)
+            }
+        },
+        Value::Int(n) => write!(f, "{}", n),
+        Value::String(s) => write!(f, "{}", s),
+        Value::Boolean(b) => write!(f, "{}", b),
+        Value::Function(func) => write!(f, "<fn {}>", func.name()),
+        Value::Nil => write!(f, "nil"),
+        Value::NativeFunction(func) => write!(f, "<native fn {}>", func.name()),
+        Value::BoundNative(bound) => write!(f, "<native fn {}>", bound.function.name()),
+        Value::HostFunction(host) => write!(f, "<native fn {}>", host.name),
+        Value::List(elements) => {
+            let address = Rc::as_ptr(elements) as usize;
+            if printing.contains(&address) {
+                return write!(f, "[...]");
+            }
+            printing.push(address);
+            write!(f, "[")?;
+            for (i, element) in elements.borrow().iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
                 }
-            },
-            Value::String(s) => write!(f, "{}", s),
-            Value::Boolean(b) => write!(f, "{}", b),
-            Value::Function(func) => write!(f, "<fn {}>", func.name()),
-            Value::Nil => write!(f, "nil"),
-            Value::NativeFunction(func) => write!(f, "<native fn {}>", func.name()),
+                write_value(f, element, precision, printing)?;
+            }
+            printing.pop();
+            write!(f, "]")
         }
+        Value::Map(entries) => {
+            let address = Rc::as_ptr(entries) as usize;
+            if printing.contains(&address) {
+                return write!(f, "{{...}}");
+            }
+            printing.push(address);
+            write!(f, "{{")?;
+            for (i, (key, value)) in entries.borrow().iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}: ", key)?;
+                write_value(f, value, precision, printing)?;
+            }
+            printing.pop();
+            write!(f, "}}")
+        }
+        Value::Module(module) => write!(f, "<module {}>", module.name),
+        Value::Range { start, end, inclusive } => write!(f, "{}..{}{}", start, if *inclusive { "=" } else { "" }, end),
     }
 }
 
@@ -65,26 +272,25 @@ impl Clone for Value {
     fn clone(&self) -> Self {
         match self {
             Value::Number(n) => Value::Number(*n),
+            Value::Int(n) => Value::Int(*n),
             Value::String(s) => Value::String(s.clone()),
             Value::Boolean(b) => Value::Boolean(*b),
             Value::Nil => Value::Nil,
             Value::Function(f) => Value::Function(f.clone()),
-            Value::NativeFunction(nf) => Value::NativeFunction(nf.clone())
+            Value::NativeFunction(nf) => Value::NativeFunction(nf.clone()),
+            Value::BoundNative(bound) => Value::BoundNative(Rc::clone(bound)),
+            Value::HostFunction(host) => Value::HostFunction(Rc::clone(host)),
+            Value::List(elements) => Value::List(Rc::clone(elements)),
+            Value::Map(entries) => Value::Map(Rc::clone(entries)),
+            Value::Module(module) => Value::Module(Rc::clone(module)),
+            Value::Range { start, end, inclusive } => Value::Range { start: *start, end: *end, inclusive: *inclusive },
         }
     }
 }
 
-// Implement PartialEq manually  
+// Same rules as Lox's ==
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Value::Number(a), Value::Number(b)) => a == b,
-            (Value::String(a), Value::String(b)) => a == b,
-            (Value::Boolean(a), Value::Boolean(b)) => a == b,
-            (Value::Nil, Value::Nil) => true,
-            (Value::Function(a), Value::Function(b)) => a == b,
-            (Value::NativeFunction(a), Value::NativeFunction(b)) => a.name() == b.name(),
-            _ => false,
-        }
+        self.is_equal(other)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,17 @@
+use rlox::{Interpreter, RuntimeError, Value};
+
+#[test]
+fn assert_eq_passes_on_equal_values() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(interpreter.eval_str("assertEq(1 + 2, 3)").unwrap(), Value::Nil);
+    assert_eq!(interpreter.eval_str("assertEq(\"ab\", \"a\" + \"b\")").unwrap(), Value::Nil);
+    assert_eq!(interpreter.eval_str("assertEq(2, 2.0)").unwrap(), Value::Nil);
+}
+
+#[test]
+fn assert_eq_reports_both_values() {
+    let err = Interpreter::new().eval_str("assertEq(2 + 2, 3);").unwrap_err();
+    let err = err.downcast::<RuntimeError>().unwrap();
+    assert_eq!(err.message, "Assertion failed: expected 3 but got 4.");
+    assert_eq!(err.token.line, 1);
+}
@@ -0,0 +1,50 @@
+use rlox::ast_printer::AstPrinter;
+use rlox::{Interpreter, Parser, Scanner, Value};
+
+#[test]
+fn chained_assignment_is_right_associative() {
+    let tokens = Scanner::new("a = b = c = 0".to_string()).scan_tokens().unwrap().clone();
+    let expr = Parser::new(tokens).parse_expression().unwrap();
+    assert_eq!(AstPrinter::new().print(&expr), "(= a (= b (= c 0)))");
+}
+
+#[test]
+fn chained_assignment_sets_every_variable() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var a = 1; var b = 2;").unwrap();
+    assert_eq!(interpreter.eval_str("a = b = 5").unwrap(), Value::Int(5));
+    assert_eq!(interpreter.eval_str("a").unwrap(), Value::Int(5));
+    assert_eq!(interpreter.eval_str("b").unwrap(), Value::Int(5));
+}
+
+#[test]
+fn chained_assignment_reaches_enclosing_scopes() {
+    let mut interpreter = Interpreter::new();
+    let source = "
+        var a; var b; var c;
+        fun reset() { var b = 1; a = b = c = 0; return b; }
+        var inner = reset();
+    ";
+    interpreter.eval_str(source).unwrap();
+    assert_eq!(interpreter.eval_str("inner").unwrap(), Value::Int(0)); // the local b
+    assert_eq!(interpreter.eval_str("a").unwrap(), Value::Int(0));
+    assert_eq!(interpreter.eval_str("b").unwrap(), Value::Nil); // the global b was shadowed
+    assert_eq!(interpreter.eval_str("c").unwrap(), Value::Int(0));
+}
+
+#[test]
+fn chain_mixing_variables_and_indexes() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var xs = [0, 0]; var y; xs[0] = y = xs[1] = 7;").unwrap();
+    assert_eq!(interpreter.eval_str("xs").unwrap().to_string(), "[7, 7]");
+    assert_eq!(interpreter.eval_str("y").unwrap(), Value::Int(7));
+}
+
+#[test]
+fn chain_with_an_undefined_target_fails_before_assigning_the_rest() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var a = 1;").unwrap();
+    let err = interpreter.eval_str("a = missing = 2;").unwrap_err();
+    assert!(err.to_string().contains("Undefined variable 'missing'."));
+    assert_eq!(interpreter.eval_str("a").unwrap(), Value::Int(1));
+}
@@ -0,0 +1,75 @@
+use rlox::ast_printer::AstPrinter;
+use rlox::stmt::Stmt;
+use rlox::{ErrorReporter, Interpreter, Parser, RuntimeError, Scanner, Value};
+use std::fs;
+
+fn parse(source: &str) -> String {
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let statements = Parser::new(tokens).parse(&mut ErrorReporter::new()).unwrap();
+    let Some(Stmt::Expression { expression }) = statements.into_iter().next() else { panic!("expected an expression") };
+    AstPrinter::new().print(&expression)
+}
+
+fn parse_errors(source: &str) -> Vec<String> {
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let mut parser = Parser::new(tokens);
+    parser.parse(&mut ErrorReporter::new());
+    parser.errors().iter().map(|err| err.message.clone()).collect()
+}
+
+#[test]
+fn property_targets_become_set_nodes() {
+    assert_eq!(parse("obj.field = 1;"), "(.= obj field 1)");
+    assert_eq!(parse("a.b.c = 1;"), "(.= (. a b) c 1)");
+    assert_eq!(parse("list[0].x = 1;"), "(.= (index list 0) x 1)");
+}
+
+#[test]
+fn index_targets_become_index_set_nodes() {
+    assert_eq!(parse("list[0] = 2;"), "(index= list 0 2)");
+    assert_eq!(parse("obj.items[1] = 2;"), "(index= (. obj items) 1 2)");
+}
+
+#[test]
+fn assignment_is_right_associative_through_properties() {
+    assert_eq!(parse("a.x = b[0] = 3;"), "(.= a x (index= b 0 3))");
+}
+
+#[test]
+fn other_targets_are_errors() {
+    assert_eq!(parse_errors("1 + 2 = 3;"), ["Invalid assignment target. at '='"]);
+    assert_eq!(parse_errors("f() = 3;"), ["Invalid assignment target. at '='"]);
+    assert_eq!(parse_errors("a?.b = 3;"), ["Invalid assignment target. at '='"]);
+}
+
+fn module_interpreter(name: &str) -> Interpreter {
+    let dir = std::env::temp_dir().join(format!("rlox-set-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("counter.lox"), "var count = 0; fun current() { return count; }").unwrap();
+    let mut interpreter = Interpreter::new();
+    interpreter.set_script_path(dir.join("main.lox"));
+    interpreter.eval_str(r#"import "counter.lox" as c;"#).unwrap();
+    interpreter
+}
+
+#[test]
+fn setting_a_module_variable() {
+    let mut interpreter = module_interpreter("module");
+    assert_eq!(interpreter.eval_str("c.count = 5").unwrap(), Value::Int(5));
+    assert_eq!(interpreter.eval_str("c.count").unwrap(), Value::Int(5));
+    // The module's own functions see the change
+    assert_eq!(interpreter.eval_str("c.current()").unwrap(), Value::Int(5));
+}
+
+#[test]
+fn setting_a_missing_module_variable_is_an_error() {
+    let mut interpreter = module_interpreter("missing");
+    let err = interpreter.eval_str("c.missing = 1;").unwrap_err().downcast::<RuntimeError>().unwrap();
+    assert_eq!(err.message, "Undefined property 'missing' in module 'c'.");
+}
+
+#[test]
+fn only_modules_have_settable_properties() {
+    let err = Interpreter::new().eval_str("var s = \"abc\"; s.length = 1;").unwrap_err();
+    assert_eq!(err.downcast::<RuntimeError>().unwrap().message, "Only modules have properties that can be set.");
+}
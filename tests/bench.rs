@@ -0,0 +1,43 @@
+use rlox::{Interpreter, Value};
+
+fn error(source: &str) -> String {
+    Interpreter::new().eval_str(source).unwrap_err().to_string()
+}
+
+#[test]
+fn bench_calls_the_function_each_iteration() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var calls = 0; fun tick() { calls = calls + 1; } var elapsed = bench(tick, 5);").unwrap();
+    assert_eq!(interpreter.eval_str("calls").unwrap(), Value::Int(5));
+    match interpreter.eval_str("elapsed").unwrap() {
+        Value::Number(seconds) => assert!(seconds >= 0.0, "{}", seconds),
+        other => panic!("expected a number of seconds, got {}", other),
+    }
+}
+
+#[test]
+fn bench_ignores_a_fake_clock() {
+    let mut interpreter = Interpreter::with_clock(|| 1000.0);
+    let elapsed = interpreter.eval_str("fun nothing() {} bench(nothing, 3)").unwrap();
+    assert!(matches!(elapsed, Value::Number(seconds) if (0.0..1000.0).contains(&seconds)), "{}", elapsed);
+}
+
+#[test]
+fn zero_iterations_never_calls() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var calls = 0; fun tick() { calls = calls + 1; } bench(tick, 0);").unwrap();
+    assert_eq!(interpreter.eval_str("calls").unwrap(), Value::Int(0));
+}
+
+#[test]
+fn bench_checks_its_arguments() {
+    assert!(error("bench(1, 10)").contains("Argument to bench() must be a function."));
+    assert!(error("fun f() {} bench(f, \"ten\")").contains("Argument to bench() must be an integer."));
+    assert!(error("fun f() {} bench(f, -1)").contains("Can't bench a negative number of iterations."));
+    assert!(error("fun f(x) {} bench(f, 1)").contains("Expected 1 arguments but got 0."));
+}
+
+#[test]
+fn errors_inside_the_benched_function_stop_the_bench() {
+    assert!(error("fun boom() { return 1 + nil; } bench(boom, 3)").contains("Operands"));
+}
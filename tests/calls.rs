@@ -0,0 +1,72 @@
+use rlox::ast_printer::AstPrinter;
+use rlox::expr::Expr;
+use rlox::stmt::Stmt;
+use rlox::{ErrorReporter, Parser, Scanner};
+
+fn parse_expr(source: &str) -> Expr {
+    let tokens = Scanner::new(format!("{};", source)).scan_tokens().unwrap().clone();
+    let statements = Parser::new(tokens).parse(&mut ErrorReporter::new()).unwrap();
+    let Some(Stmt::Expression { expression }) = statements.into_iter().next() else { panic!("expected an expression") };
+    *expression
+}
+
+fn parse_errors(source: &str) -> Vec<String> {
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let mut parser = Parser::new(tokens);
+    parser.parse(&mut ErrorReporter::new());
+    parser.errors().iter().map(|err| err.message.clone()).collect()
+}
+
+#[test]
+fn calls_with_and_without_arguments() {
+    assert_eq!(AstPrinter::new().print(&parse_expr("f()")), "(call f)");
+    assert_eq!(AstPrinter::new().print(&parse_expr("f(1, 2)")), "(call f 1 2)");
+    assert_eq!(AstPrinter::new().print(&parse_expr("f(g(1))")), "(call f (call g 1))");
+    assert_eq!(AstPrinter::new().print(&parse_expr("f(1)(2)")), "(call (call f 1) 2)");
+}
+
+#[test]
+fn paren_is_the_closing_parenthesis() {
+    let Expr::Call { paren, arguments, .. } = parse_expr("f(1,\n 2)") else { panic!("expected a call") };
+    assert_eq!(paren.lexeme, ")");
+    assert_eq!(paren.line, 2);
+    assert_eq!(arguments.len(), 2);
+}
+
+#[test]
+fn at_most_255_arguments() {
+    let arguments = |n: usize| vec!["0"; n].join(", ");
+    assert!(parse_errors(&format!("f({});", arguments(255))).is_empty());
+    assert_eq!(parse_errors(&format!("f({});", arguments(256))), ["Can't have more than 255 arguments. at '0'"]);
+}
+
+#[test]
+fn unclosed_argument_list() {
+    let errors = parse_errors("f(1, 2;");
+    assert!(errors[0].starts_with("Expect ')' after arguments."), "{:?}", errors);
+}
+
+fn runtime_error(interpreter: &mut rlox::Interpreter, source: &str) -> rlox::RuntimeError {
+    interpreter.eval_str(source).unwrap_err().downcast::<rlox::RuntimeError>().unwrap()
+}
+
+#[test]
+fn arity_errors_count_the_evaluated_arguments() {
+    let mut interpreter = rlox::Interpreter::new();
+    interpreter.eval_str("fun two(a, b) {}").unwrap();
+    assert_eq!(runtime_error(&mut interpreter, "two(1);").message, "Expected 2 arguments but got 1.");
+    assert_eq!(runtime_error(&mut interpreter, "two(1, 2, 3);").message, "Expected 2 arguments but got 3.");
+    assert_eq!(runtime_error(&mut interpreter, "len();").message, "Expected 1 arguments but got 0.");
+    assert_eq!(runtime_error(&mut interpreter, "\"abc\".contains(\"a\", \"b\");").message, "Expected 1 arguments but got 2.");
+
+    let err = runtime_error(&mut interpreter, "two(\n1);");
+    assert_eq!((err.token.lexeme.as_str(), err.token.line), (")", 2));
+}
+
+#[test]
+fn arguments_are_evaluated_before_the_arity_check() {
+    let mut interpreter = rlox::Interpreter::new();
+    interpreter.eval_str("var n = 0; fun bump() { n = n + 1; return n; } fun one(a) {}").unwrap();
+    assert!(interpreter.eval_str("one(bump(), bump());").is_err());
+    assert_eq!(interpreter.eval_str("n").unwrap(), rlox::Value::Int(2));
+}
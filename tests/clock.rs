@@ -0,0 +1,36 @@
+use rlox::native::ClockSource;
+use rlox::{Interpreter, Value};
+use std::cell::Cell;
+
+#[test]
+fn injected_clock_is_what_clock_returns() {
+    let mut interpreter = Interpreter::with_clock(|| 42.0);
+    let now = interpreter.eval_str("clock()").unwrap();
+    assert_eq!(now, Value::Number(42.0));
+    assert_eq!(interpreter.stringify(&now), "42");
+}
+
+// Moves forward half a second every time it is read
+struct SteppingClock {
+    time: Cell<f64>,
+}
+
+impl ClockSource for SteppingClock {
+    fn now(&self) -> f64 {
+        self.time.set(self.time.get() + 0.5);
+        self.time.get()
+    }
+}
+
+#[test]
+fn elapsed_time_with_a_fake_clock() {
+    let mut interpreter = Interpreter::with_clock(SteppingClock { time: Cell::new(10.0) });
+    let elapsed = interpreter.eval_str("var start = clock(); clock() - start").unwrap();
+    assert_eq!(elapsed, Value::Number(0.5));
+}
+
+#[test]
+fn default_clock_is_real_time() {
+    let now = Interpreter::new().eval_str("clock()").unwrap();
+    assert!(matches!(now, Value::Number(seconds) if seconds > 1_600_000_000.0));
+}
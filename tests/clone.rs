@@ -0,0 +1,40 @@
+use rlox::{Interpreter, Value};
+
+fn eval(interpreter: &mut Interpreter, source: &str) -> Value {
+    interpreter.eval_str(source).unwrap()
+}
+
+#[test]
+fn mutating_a_clone_leaves_the_original_alone() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str(r#"var a = [1, [2, 3], {"k": [4]}]; var b = clone(a); b[0] = 10; b[1][0] = 20; b[2]["k"][0] = 40;"#).unwrap();
+    assert_eq!(eval(&mut interpreter, "a[0]"), Value::Int(1));
+    assert_eq!(eval(&mut interpreter, "a[1][0]"), Value::Int(2));
+    assert_eq!(eval(&mut interpreter, r#"a[2]["k"][0]"#), Value::Int(4));
+    assert_eq!(eval(&mut interpreter, "b[1][0]"), Value::Int(20));
+}
+
+#[test]
+fn scalars_come_back_unchanged() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(eval(&mut interpreter, "clone(1)"), Value::Int(1));
+    assert_eq!(eval(&mut interpreter, r#"clone("s")"#), Value::String("s".to_string()));
+    assert_eq!(eval(&mut interpreter, "clone(nil)"), Value::Nil);
+}
+
+#[test]
+fn cyclic_list_clones_into_a_new_cycle() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var a = [1, 2]; a[1] = a; var b = clone(a); b[0] = 5;").unwrap();
+    // The copy points at itself, not back at the original
+    assert_eq!(eval(&mut interpreter, "b[1][1][0]"), Value::Int(5));
+    assert_eq!(eval(&mut interpreter, "a[0]"), Value::Int(1));
+}
+
+#[test]
+fn shared_elements_stay_shared_in_the_copy() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var inner = [0]; var b = clone([inner, inner]); b[0][0] = 7;").unwrap();
+    assert_eq!(eval(&mut interpreter, "b[1][0]"), Value::Int(7));
+    assert_eq!(eval(&mut interpreter, "inner[0]"), Value::Int(0));
+}
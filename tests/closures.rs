@@ -0,0 +1,172 @@
+use rlox::{Interpreter, Value};
+
+const MAKE_COUNTER: &str = "fun makeCounter() { var i = 0; fun count() { i = i + 1; return i; } return count; }";
+
+#[test]
+fn counter_state_persists_across_calls() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str(MAKE_COUNTER).unwrap();
+    interpreter.eval_str("var counter = makeCounter();").unwrap();
+    assert_eq!(interpreter.eval_str("counter()").unwrap(), Value::Int(1));
+    assert_eq!(interpreter.eval_str("counter()").unwrap(), Value::Int(2));
+    assert_eq!(interpreter.eval_str("counter()").unwrap(), Value::Int(3));
+}
+
+#[test]
+fn each_counter_has_its_own_state() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str(MAKE_COUNTER).unwrap();
+    interpreter.eval_str("var a = makeCounter(); var b = makeCounter(); a(); a();").unwrap();
+    assert_eq!(interpreter.eval_str("b()").unwrap(), Value::Int(1));
+    assert_eq!(interpreter.eval_str("a()").unwrap(), Value::Int(3));
+}
+
+#[test]
+fn closure_sees_later_changes_to_captured_variable() {
+    let mut interpreter = Interpreter::new();
+    let result = interpreter
+        .eval_str("var x = \"before\"; fun show() { return x; } x = \"after\"; show()")
+        .unwrap();
+    assert_eq!(result, Value::String("after".to_string()));
+}
+
+#[test]
+fn closures_share_the_variable_they_capture() {
+    let mut interpreter = Interpreter::new();
+    interpreter
+        .eval_str("var get; var set; { var value = 1; fun g() { return value; } fun s(v) { value = v; } get = g; set = s; }")
+        .unwrap();
+    interpreter.eval_str("set(42);").unwrap();
+    assert_eq!(interpreter.eval_str("get()").unwrap(), Value::Int(42));
+}
+
+#[test]
+fn mutual_recursion() {
+    let mut interpreter = Interpreter::new();
+    interpreter
+        .eval_str("fun isEven(n) { if (n == 0) return true; return isOdd(n - 1); } fun isOdd(n) { if (n == 0) return false; return isEven(n - 1); }")
+        .unwrap();
+    assert_eq!(interpreter.eval_str("isEven(10)").unwrap(), Value::Boolean(true));
+    assert_eq!(interpreter.eval_str("isOdd(7)").unwrap(), Value::Boolean(true));
+    assert_eq!(interpreter.eval_str("isEven(3)").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn parameter_named_like_the_function() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(interpreter.eval_str("fun f(f) { return f * 2; } f(21)").unwrap(), Value::Int(42));
+}
+
+#[test]
+fn function_name_does_not_shadow_rebinding_in_its_scope() {
+    // Re-pointing the name at another function is seen by the recursive call
+    let mut interpreter = Interpreter::new();
+    let result = interpreter
+        .eval_str("fun count(n) { if (n == 0) return \"first\"; return count(n - 1); } var original = count; fun count(n) { return \"second\"; } original(1)")
+        .unwrap();
+    assert_eq!(result, Value::String("second".to_string()));
+}
+
+#[test]
+fn local_recursive_function() {
+    let mut interpreter = Interpreter::new();
+    let result = interpreter
+        .eval_str("fun outer() { fun fact(n) { if (n <= 1) return 1; return n * fact(n - 1); } return fact(5); } outer()")
+        .unwrap();
+    assert_eq!(result, Value::Int(120));
+}
+
+#[test]
+fn functions_and_natives_can_be_called_through_variables() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(interpreter.eval_str("fun add(a, b) { return a + b; } var plus = add; plus(2, 3)").unwrap(), Value::Int(5));
+    assert_eq!(interpreter.eval_str("var up = toUpper; up(\"abc\")").unwrap(), Value::String("ABC".to_string()));
+    assert!(matches!(interpreter.eval_str("var c = clock; c()").unwrap(), Value::Number(_)));
+}
+
+#[test]
+fn natives_can_be_passed_and_returned() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("fun apply(f, x) { return f(x); } fun pick() { return toLower; }").unwrap();
+    assert_eq!(interpreter.eval_str("apply(trim, \"  x \")").unwrap(), Value::String("x".to_string()));
+    assert_eq!(interpreter.eval_str("pick()(\"ABC\")").unwrap(), Value::String("abc".to_string()));
+}
+
+#[test]
+fn closures_made_in_a_for_loop_keep_their_own_counter() {
+    let mut interpreter = Interpreter::new();
+    let source = "
+        var fns = [nil, nil, nil];
+        for (var i = 0; i < 3; i = i + 1) {
+            fun get() { return i; }
+            fns[i] = get;
+        }
+    ";
+    interpreter.eval_str(source).unwrap();
+    assert_eq!(interpreter.eval_str("str(fns[0](), \",\", fns[1](), \",\", fns[2]())").unwrap().to_string(), "0,1,2");
+}
+
+#[test]
+fn changes_in_the_body_carry_over_to_the_next_iteration() {
+    let mut interpreter = Interpreter::new();
+    let source = "
+        var count = 0;
+        for (var i = 0; i < 10; i = i + 1) {
+            i = i + 2; // skips ahead, like it would without per-iteration copies
+            count = count + 1;
+        }
+        count
+    ";
+    assert_eq!(interpreter.eval_str(source).unwrap(), Value::Int(4));
+}
+
+#[test]
+fn calls_chain_for_curried_functions() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("fun makeAdder(a) { fun add(b) { return a + b; } return add; }").unwrap();
+    assert_eq!(interpreter.eval_str("makeAdder(3)(4)").unwrap(), Value::Int(7));
+
+    // Three levels deep, each call closing over the arguments before it
+    interpreter.eval_str("fun sum3(a) { fun f(b) { fun g(c) { return a + b + c; } return g; } return f; }").unwrap();
+    assert_eq!(interpreter.eval_str("sum3(1)(2)(3)").unwrap(), Value::Int(6));
+    assert_eq!(interpreter.eval_str("var partial = sum3(10)(20); partial(1) + partial(2)").unwrap(), Value::Int(63));
+}
+
+#[test]
+fn plain_recursion_finds_the_function_through_its_closure() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("fun fact(n) { if (n <= 1) return 1; return n * fact(n - 1); }").unwrap();
+    assert_eq!(interpreter.eval_str("fact(10)").unwrap(), Value::Int(3628800));
+}
+
+#[test]
+fn reassigning_the_variable_holding_a_function_keeps_its_recursion() {
+    // The recursive call looks up "fact", not whatever variable the function was called through
+    let mut interpreter = Interpreter::new();
+    let result = interpreter
+        .eval_str("fun fact(n) { if (n <= 1) return 1; return n * fact(n - 1); } var f = fact; var saved = f; f = nil; saved(5)")
+        .unwrap();
+    assert_eq!(result, Value::Int(120));
+}
+
+#[test]
+fn a_function_argument_can_shadow_the_function_name() {
+    // With the function defined in each call's environment, apply(3) would call itself here
+    let mut interpreter = Interpreter::new();
+    let result = interpreter
+        .eval_str("fun double(x) { return x * 2; } fun apply(apply) { return apply(3); } apply(double)")
+        .unwrap();
+    assert_eq!(result, Value::Int(6));
+}
+
+#[test]
+fn calls_dont_define_the_function_in_their_own_environment() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("fun make() { fun get() { return 1; } return get; } var g = make();").unwrap();
+    let call_env = match interpreter.eval_str("g").unwrap() {
+        Value::Function(function) => function.closure(),
+        other => panic!("g is not a function: {}", other),
+    };
+    assert!(interpreter.is_defined_local(call_env, "get"));
+    assert!(!interpreter.is_defined_local(call_env, "make"));
+}
@@ -0,0 +1,131 @@
+use rlox::{Interpreter, RuntimeError};
+
+fn eval(interpreter: &mut Interpreter, source: &str) -> String {
+    interpreter.eval_str(source).unwrap().to_string()
+}
+
+#[test]
+fn nested_list_assignment() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var a = [[1, 2], [3, 4]]; a[0][1] = 5;").unwrap();
+    assert_eq!(eval(&mut interpreter, "a"), "[[1, 5], [3, 4]]");
+    assert_eq!(eval(&mut interpreter, "a[1][0]"), "3");
+}
+
+#[test]
+fn assignment_evaluates_to_assigned_value() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(eval(&mut interpreter, "var a = [0]; a[0] = 7"), "7");
+}
+
+#[test]
+fn map_of_lists_update() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str(r#"var m = {"xs": [1, 2], "y": {"z": 0}};"#).unwrap();
+    interpreter.eval_str(r#"m["xs"][1] = 20; m["y"]["z"] = "deep"; m["new"] = true;"#).unwrap();
+    assert_eq!(eval(&mut interpreter, r#"m["xs"]"#), "[1, 20]");
+    assert_eq!(eval(&mut interpreter, r#"m["y"]["z"]"#), "deep");
+    assert_eq!(eval(&mut interpreter, "m"), "{new: true, xs: [1, 20], y: {z: deep}}");
+}
+
+#[test]
+fn lists_are_shared_by_reference() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(eval(&mut interpreter, "var a = [1]; var b = a; b[0] = 2; a[0]"), "2");
+}
+
+#[test]
+fn out_of_range_errors_at_each_level() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var a = [[1, 2], [3]];").unwrap();
+
+    let outer = interpreter.eval_str("a[2][0] = 1;").unwrap_err();
+    assert!(outer.to_string().contains("List index 2 is out of range for length 2."));
+
+    let inner = interpreter.eval_str("a[1][1] = 1;").unwrap_err();
+    assert!(inner.to_string().contains("List index 1 is out of range for length 1."));
+
+    let read = interpreter.eval_str(r#"var m = {"xs": []}; m["xs"][0];"#).unwrap_err();
+    assert!(read.to_string().contains("List index 0 is out of range for length 0."));
+
+    let key = interpreter.eval_str(r#"m["nope"][0];"#).unwrap_err();
+    assert!(key.to_string().contains("Undefined key 'nope'."));
+}
+
+#[test]
+fn only_lists_and_maps_can_be_indexed() {
+    let mut interpreter = Interpreter::new();
+    assert!(interpreter.eval_str("var n = 1; n[0];").is_err());
+    assert!(interpreter.eval_str("[1][true];").is_err());
+}
+
+#[test]
+fn destructure_list_into_variables() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(eval(&mut interpreter, "var [a, b, c] = [1, 2, 3]; a * 100 + b * 10 + c"), "123");
+    assert_eq!(eval(&mut interpreter, "var [first, ...rest] = [1, 2, 3]; rest"), "[2, 3]");
+    assert_eq!(eval(&mut interpreter, "var [x, ...tail] = [1]; tail"), "[]");
+}
+
+#[test]
+fn destructure_length_mismatch_is_an_error() {
+    let err = Interpreter::new().eval_str("var [a, b] = [1, 2, 3];").unwrap_err();
+    let err = err.downcast::<RuntimeError>().unwrap();
+    assert_eq!(err.message, "Expected 2 values to destructure but got 3.");
+
+    let err = Interpreter::new().eval_str("var [a, b, ...rest] = [1];").unwrap_err();
+    assert_eq!(err.downcast::<RuntimeError>().unwrap().message, "Expected at least 2 values to destructure but got 1.");
+
+    assert!(Interpreter::new().eval_str("var [a] = 1;").is_err());
+}
+
+#[test]
+fn lists_and_maps_compare_by_contents() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(eval(&mut interpreter, "[1, 2] == [1, 2]"), "true");
+    assert_eq!(eval(&mut interpreter, "[1, 2] == [1, 3]"), "false");
+    assert_eq!(eval(&mut interpreter, "[1, 2] == [1, 2, 3]"), "false");
+    assert_eq!(eval(&mut interpreter, "[[1, [2]], 3] == [[1, [2]], 3]"), "true");
+    assert_eq!(eval(&mut interpreter, "[[1, [2]], 3] != [[1, [4]], 3]"), "true");
+    assert_eq!(eval(&mut interpreter, "({\"a\": [1], \"b\": 2} == {\"b\": 2, \"a\": [1]})"), "true");
+    assert_eq!(eval(&mut interpreter, "({\"a\": 1} == {\"b\": 1})"), "false");
+}
+
+#[test]
+fn self_referencing_lists_can_be_compared() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var a = [1, 0]; a[1] = a; var b = [1, 0]; b[1] = b;").unwrap();
+    assert_eq!(eval(&mut interpreter, "a == b"), "true");
+    assert_eq!(eval(&mut interpreter, "a == a"), "true");
+    interpreter.eval_str("var c = [2, 0]; c[1] = c;").unwrap();
+    assert_eq!(eval(&mut interpreter, "a == c"), "false");
+}
+
+#[test]
+fn self_referencing_lists_and_maps_can_be_printed() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var a = [1]; push(a, a); var m = {\"n\": 1}; m[\"self\"] = m;").unwrap();
+    assert_eq!(eval(&mut interpreter, "a"), "[1, [...]]");
+    assert_eq!(eval(&mut interpreter, "m"), "{n: 1, self: {...}}");
+    assert_eq!(eval(&mut interpreter, "str(a)"), "[1, [...]]");
+    assert_eq!(eval(&mut interpreter, "repr(m)"), "{n: 1, self: {...}}");
+
+    // A map inside a list inside that map only cuts off where the cycle closes
+    interpreter.eval_str("var outer = {\"items\": []}; push(outer[\"items\"], outer);").unwrap();
+    assert_eq!(eval(&mut interpreter, "outer"), "{items: [{...}]}");
+}
+
+#[test]
+fn shared_elements_that_arent_cycles_print_in_full() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(eval(&mut interpreter, "var b = [1, 2]; [b, b]"), "[[1, 2], [1, 2]]");
+}
+
+#[test]
+fn printing_a_cycle_from_a_script_doesnt_crash() {
+    let path = std::env::temp_dir().join("rlox_print_cycle.lox");
+    std::fs::write(&path, "var a = [1]; push(a, a); print a;\nvar m = {}; m[\"self\"] = m; print m;\n").unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rlox")).arg(&path).output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "[1, [...]]\n{self: {...}}\n");
+}
@@ -0,0 +1,50 @@
+use rlox::{Interpreter, Value};
+
+#[test]
+fn accessors_on_matching_values() {
+    assert_eq!(Value::Number(2.5).as_number(), Some(2.5));
+    assert_eq!(Value::Int(2).as_number(), Some(2.0));
+    assert_eq!(Value::String("hi".to_string()).as_string(), Some("hi"));
+    assert_eq!(Value::Boolean(false).as_bool(), Some(false));
+}
+
+#[test]
+fn accessors_on_other_values() {
+    assert_eq!(Value::String("1".to_string()).as_number(), None);
+    assert_eq!(Value::Nil.as_number(), None);
+    assert_eq!(Value::Int(1).as_string(), None);
+    assert_eq!(Value::Nil.as_bool(), None); // falsy, but not a boolean
+    assert_eq!(Value::Int(0).as_bool(), None);
+}
+
+#[test]
+fn from_rust_values() {
+    assert_eq!(Value::from(1.5), Value::Number(1.5));
+    assert_eq!(Value::from(3i64), Value::Int(3));
+    assert_eq!(Value::from(true), Value::Boolean(true));
+    assert_eq!(Value::from("a"), Value::String("a".to_string()));
+    assert_eq!(Value::from("b".to_string()), Value::String("b".to_string()));
+}
+
+#[test]
+fn try_into_rust_values() {
+    assert_eq!(f64::try_from(Value::Int(4)).unwrap(), 4.0);
+    assert!(bool::try_from(Value::Boolean(true)).unwrap());
+    assert_eq!(String::try_from(Value::from("s")).unwrap(), "s");
+
+    let err = f64::try_from(Value::from("four")).unwrap_err();
+    assert_eq!(err.to_string(), "Expected a number but got four.");
+    assert!(bool::try_from(Value::Nil).is_err());
+    assert!(String::try_from(Value::Int(1)).is_err());
+}
+
+#[test]
+fn host_function_using_conversions() {
+    let mut interpreter = Interpreter::new();
+    interpreter.define_native("half", 1, |_, arguments| {
+        let n = f64::try_from(arguments[0].clone())?;
+        Ok(Value::from(n / 2.0))
+    });
+    assert_eq!(interpreter.eval_str("half(5)").unwrap(), Value::Number(2.5));
+    assert!(interpreter.eval_str("half(\"x\")").is_err());
+}
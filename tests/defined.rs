@@ -0,0 +1,93 @@
+use rlox::{Interpreter, RuntimeError, RuntimeErrorKind, Value};
+
+fn closure_of(interpreter: &mut Interpreter, name: &str) -> usize {
+    match interpreter.eval_str(name).unwrap() {
+        Value::Function(function) => function.closure(),
+        other => panic!("{} is not a function: {}", name, other),
+    }
+}
+
+// get's closure is the environment of the makeGetter() call, whose parent is the globals
+const NESTED: &str = "
+var outer = 1;
+fun makeGetter() {
+    var inner = 2;
+    fun get() { return inner; }
+    return get;
+}
+var g = makeGetter();
+";
+
+#[test]
+fn local_check_only_looks_at_one_environment() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str(NESTED).unwrap();
+    let closure = closure_of(&mut interpreter, "g");
+
+    assert!(interpreter.is_defined_local(closure, "inner"));
+    assert!(interpreter.is_defined_local(closure, "get"));
+    assert!(!interpreter.is_defined_local(closure, "outer"));
+}
+
+#[test]
+fn chained_check_walks_up_to_the_globals() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str(NESTED).unwrap();
+    let closure = closure_of(&mut interpreter, "g");
+
+    assert!(interpreter.is_defined(closure, "inner"));
+    assert!(interpreter.is_defined(closure, "outer"));
+    assert!(interpreter.is_defined(closure, "clock"), "natives live in the globals too");
+    assert!(!interpreter.is_defined(closure, "missing"));
+}
+
+#[test]
+fn parents_cant_see_into_children() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str(NESTED).unwrap();
+    let globals = interpreter.current_env();
+
+    assert!(interpreter.is_defined_local(globals, "outer"));
+    assert!(!interpreter.is_defined(globals, "inner"));
+}
+
+#[test]
+fn nil_still_counts_as_defined() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var nothing;").unwrap();
+    let globals = interpreter.current_env();
+
+    assert!(interpreter.is_defined(globals, "nothing"));
+    assert!(!interpreter.is_defined(globals, "something"));
+}
+
+#[test]
+fn declared_without_a_value_reads_as_nil() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(interpreter.eval_str("var x; x").unwrap(), Value::Nil);
+    assert_eq!(interpreter.eval_str("{ var local; local }").unwrap(), Value::Nil);
+}
+
+#[test]
+fn reading_an_undefined_name_is_an_error() {
+    let mut interpreter = Interpreter::new();
+    let err = interpreter.eval_str("y").unwrap_err().downcast::<RuntimeError>().unwrap();
+    assert_eq!(err.kind(), RuntimeErrorKind::UndefinedVariable);
+    assert_eq!(err.message, "Undefined variable 'y'.");
+    // Declaring it later makes it readable
+    assert_eq!(interpreter.eval_str("var y; y").unwrap(), Value::Nil);
+}
+
+#[test]
+fn get_or_nil_reads_missing_names_as_nil() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var x; var one = 1;").unwrap();
+    let globals = interpreter.current_env();
+
+    assert_eq!(interpreter.get_or_nil(globals, "one"), Value::Int(1));
+    assert_eq!(interpreter.get_or_nil(globals, "x"), Value::Nil);
+    assert_eq!(interpreter.get_or_nil(globals, "missing"), Value::Nil);
+    // Only is_defined can tell those last two apart
+    assert!(interpreter.is_defined(globals, "x"));
+    assert!(!interpreter.is_defined(globals, "missing"));
+}
@@ -0,0 +1,20 @@
+use rlox::{Interpreter, Value};
+
+#[test]
+fn dump_globals_lists_user_variables_sorted() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var b = \"two\"; var a = 1; var c = a + 2; { var local = 4; }").unwrap();
+
+    let globals = interpreter.dump_globals();
+    let names: Vec<&str> = globals.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["a", "b", "c"]);
+    assert_eq!(globals[0].1, Value::Int(1));
+    assert_eq!(globals[1].1, Value::String("two".to_string()));
+    assert_eq!(globals[2].1, Value::Int(3));
+}
+
+#[test]
+fn dump_globals_leaves_out_natives() {
+    let interpreter = Interpreter::new();
+    assert!(interpreter.dump_globals().is_empty());
+}
@@ -0,0 +1,35 @@
+use rlox::stmt::Stmt;
+use rlox::{ErrorReporter, Interpreter, Parser, Scanner, Value};
+
+fn parse(source: &str) -> Vec<Stmt> {
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let mut parser = Parser::new(tokens);
+    let statements = parser.parse(&mut ErrorReporter::new());
+    assert!(parser.errors().is_empty(), "{:?}", parser.errors());
+    statements.unwrap()
+}
+
+#[test]
+fn lone_semicolons_are_empty_statements() {
+    let statements = parse(";;;");
+    assert_eq!(statements.len(), 3);
+    assert!(statements.iter().all(|stmt| matches!(stmt, Stmt::Empty)));
+}
+
+#[test]
+fn loop_with_an_empty_body() {
+    let statements = parse("while (false) ;");
+    let Stmt::While { body, .. } = &statements[0] else { panic!("expected a while loop") };
+    assert!(matches!(**body, Stmt::Empty));
+
+    let statements = parse("for (var i = 0; i < 3; i = i + 1) ;");
+    let Stmt::For { body, .. } = &statements[0] else { panic!("expected a for loop") };
+    assert!(matches!(**body, Stmt::Empty));
+}
+
+#[test]
+fn empty_statements_do_nothing() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var x = 1;; ; { ; x = x + 1;; }").unwrap();
+    assert_eq!(interpreter.eval_str("x").unwrap(), Value::Int(2));
+}
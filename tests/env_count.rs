@@ -0,0 +1,20 @@
+use rlox::{Interpreter, Value};
+
+fn count(interpreter: &mut Interpreter) -> i64 {
+    match interpreter.eval_str("envCount()").unwrap() {
+        Value::Int(n) => n,
+        other => panic!("envCount() gave {:?}", other),
+    }
+}
+
+#[test]
+fn blocks_in_a_loop_add_environments() {
+    let mut interpreter = Interpreter::new();
+    let before = count(&mut interpreter);
+    interpreter.eval_str("var i = 0; while (i < 10) { var x = i; i = i + 1; }").unwrap();
+    let after = count(&mut interpreter);
+
+    // One environment per pass through the block. Once environments are freed this should shrink back.
+    assert!(after >= before + 10, "before {} after {}", before, after);
+    assert_eq!(interpreter.env_count() as i64, after);
+}
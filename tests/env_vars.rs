@@ -0,0 +1,31 @@
+use rlox::{Interpreter, Value};
+
+// Each test uses its own variable names, since tests run in parallel in one process
+#[test]
+fn setenv_then_getenv() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str(r#"setenv("RLOX_TEST_GREETING", "hello");"#).unwrap();
+    assert_eq!(interpreter.eval_str(r#"getenv("RLOX_TEST_GREETING")"#).unwrap(), Value::String("hello".to_string()));
+    assert_eq!(std::env::var("RLOX_TEST_GREETING").unwrap(), "hello");
+}
+
+#[test]
+fn missing_variable_is_nil() {
+    let value = Interpreter::new().eval_str(r#"getenv("RLOX_TEST_SURELY_NOT_SET")"#).unwrap();
+    assert_eq!(value, Value::Nil);
+}
+
+#[test]
+fn arguments_must_be_strings() {
+    let err = Interpreter::new().eval_str("getenv(1);").unwrap_err();
+    assert!(err.to_string().contains("Argument to getenv() must be a string."), "{}", err);
+    let err = Interpreter::new().eval_str(r#"setenv("RLOX_TEST_NUMBER", 1);"#).unwrap_err();
+    assert!(err.to_string().contains("Argument to setenv() must be a string."), "{}", err);
+}
+
+#[test]
+fn invalid_names_are_errors_not_panics() {
+    let err = Interpreter::new().eval_str(r#"setenv("A=B", "x");"#).unwrap_err();
+    assert!(err.to_string().contains("Invalid environment variable name 'A=B'."), "{}", err);
+    assert!(Interpreter::new().eval_str(r#"setenv("", "x");"#).is_err());
+}
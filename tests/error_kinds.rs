@@ -0,0 +1,49 @@
+use rlox::{Interpreter, RuntimeError, RuntimeErrorKind};
+
+fn runtime_error(source: &str) -> RuntimeError {
+    Interpreter::new().eval_str(source).unwrap_err().downcast::<RuntimeError>().unwrap()
+}
+
+fn kind(source: &str) -> RuntimeErrorKind {
+    runtime_error(source).kind()
+}
+
+#[test]
+fn division_by_zero_has_its_own_kind() {
+    let err = runtime_error("var x = 1;\nprint x / 0;");
+    assert_eq!(err.kind(), RuntimeErrorKind::DivisionByZero);
+    assert_eq!(err.message, "Division by zero.");
+    assert_eq!((err.line(), err.column()), (2, 9), "points at the '/'");
+}
+
+#[test]
+fn undefined_variable_has_its_own_kind() {
+    let err = runtime_error("print 1;\n  missing;");
+    assert_eq!(err.kind(), RuntimeErrorKind::UndefinedVariable);
+    assert_eq!(err.message, "Undefined variable 'missing'.");
+    assert_eq!((err.line(), err.column()), (2, 3));
+
+    assert_eq!(kind("missing = 1;"), RuntimeErrorKind::UndefinedVariable);
+}
+
+#[test]
+fn call_errors_are_told_apart() {
+    assert_eq!(kind("fun f(a) {} f();"), RuntimeErrorKind::Arity);
+    assert_eq!(kind("var x = 1; x();"), RuntimeErrorKind::TypeError);
+    assert_eq!(kind("len(1);"), RuntimeErrorKind::Native);
+}
+
+#[test]
+fn other_kinds() {
+    assert_eq!(kind("1 + nil;"), RuntimeErrorKind::TypeError);
+    assert_eq!(kind("[1, 2][5];"), RuntimeErrorKind::IndexOutOfRange);
+    assert_eq!(kind("var m = {\"a\": 1}; m[\"b\"];"), RuntimeErrorKind::UndefinedProperty);
+    assert_eq!(kind("var [a, b] = [1];"), RuntimeErrorKind::Destructure);
+    assert_eq!(kind("throw \"oops\";"), RuntimeErrorKind::Thrown);
+}
+
+#[test]
+fn errors_inside_a_function_keep_their_kind() {
+    // The call site doesn't re-wrap an error that is already a RuntimeError
+    assert_eq!(kind("fun f() { return 1 / 0; } f();"), RuntimeErrorKind::DivisionByZero);
+}
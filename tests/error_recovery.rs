@@ -0,0 +1,38 @@
+use rlox::{Interpreter, Value};
+
+// After a runtime error deep inside blocks, calls or loops, the interpreter must be back in the global scope,
+// so the next REPL line doesn't run inside a block that no longer exists.
+fn assert_back_at_top_level(setup: &str, failing: &str) {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str(setup).unwrap();
+    let top_level = interpreter.current_env();
+
+    assert!(interpreter.eval_str(failing).is_err(), "expected {:?} to fail", failing);
+    assert_eq!(interpreter.current_env(), top_level, "scope leaked after {:?}", failing);
+
+    // A new variable is a global again, visible from a function defined earlier
+    interpreter.eval_str("var after = 1; fun readAfter() { return after; }").unwrap();
+    assert_eq!(interpreter.eval_str("readAfter()").unwrap(), Value::Int(1));
+}
+
+#[test]
+fn error_in_nested_blocks() {
+    assert_back_at_top_level("", "{ var a = 1; { var b = 2; { nil + 1; } } }");
+}
+
+#[test]
+fn error_inside_a_function_call() {
+    assert_back_at_top_level("fun f(n) { { var x = n; return x + nil; } }", "{ f(1); }");
+}
+
+#[test]
+fn error_inside_loops() {
+    assert_back_at_top_level("", "for (var i = 0; i < 3; i = i + 1) { if (i == 1) undefinedName; }");
+    assert_back_at_top_level("", "var i = 0; while (true) { i = i + 1; { -\"x\"; } }");
+    assert_back_at_top_level("", "for (x in [1, 2]) { { x(); } }");
+}
+
+#[test]
+fn error_rethrown_from_a_catch_block() {
+    assert_back_at_top_level("", "try { throw 1; } catch (e) { { throw e; } }");
+}
@@ -0,0 +1,160 @@
+use rlox::{ErrorReporter, Interpreter, Parser, RuntimeError, Scanner, TokenType};
+
+#[test]
+fn caret_points_at_reported_token() {
+    let source = "var a = 1;\nvar b = a print b;\n";
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let token = tokens.iter().find(|t| t.lexeme == "print").unwrap();
+
+    let mut reporter = ErrorReporter::new();
+    reporter.set_color(false);
+    reporter.set_source(source);
+    let output = reporter.format_report(token.line, "", "Expect ';'.", Some((token.start, token.lexeme.len())));
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "[line 2] Error: Expect ';'.");
+    assert_eq!(lines[1], "2 | var b = a print b;");
+    assert_eq!(lines[2], "  |           ^^^^^");
+
+    // The carets start in the same column as the lexeme in the line above
+    let caret_column = lines[2].find('^').unwrap();
+    assert_eq!(&lines[1][caret_column..caret_column + 5], "print");
+}
+
+#[test]
+fn no_snippet_without_source() {
+    let mut reporter = ErrorReporter::new();
+    reporter.set_color(false);
+    let output = reporter.format_report(1, "", "Oops.", Some((0, 3)));
+    assert_eq!(output, "[line 1] Error: Oops.");
+}
+
+#[test]
+fn span_ending_inside_a_character_doesnt_split_it() {
+    let mut reporter = ErrorReporter::new();
+    reporter.set_color(false);
+    reporter.set_source("print \"é€\";");
+    // "é" is bytes 7..9 and "€" is 9..12, so a 3 byte span from 7 ends inside the "€"
+    let output = reporter.format_report(1, "", "Oops.", Some((7, 3)));
+    assert_eq!(output, "[line 1] Error: Oops.\n1 | print \"é€\";\n  |        ^^");
+
+    // A span running past the end of the source stops at the end of the line
+    let output = reporter.format_report(1, "", "Oops.", Some((7, 100)));
+    assert!(output.ends_with("  |        ^^^^"), "{:?}", output);
+}
+
+#[test]
+fn no_escape_codes_with_color_off() {
+    let mut reporter = ErrorReporter::new();
+    reporter.set_color(false);
+    reporter.set_source("print 1 +;");
+    let output = reporter.format_report(1, "", "Expect expression.", Some((9, 1)));
+    assert!(!output.contains('\x1b'), "{:?}", output);
+}
+
+#[test]
+fn escape_codes_with_color_on() {
+    let mut reporter = ErrorReporter::new();
+    reporter.set_color(true);
+    reporter.set_source("print 1 +;");
+    let output = reporter.format_report(1, "", "Expect expression.", Some((9, 1)));
+    assert!(output.starts_with("\x1b[2m[line 1]\x1b[0m \x1b[1;31mError\x1b[0m: Expect expression."), "{:?}", output);
+    assert!(output.contains("\x1b[1;31m^\x1b[0m"), "{:?}", output);
+}
+
+// (message, expected tokens) for each error reported while parsing
+fn parse_errors(source: &str) -> Vec<(String, Vec<TokenType>)> {
+    let mut scanner = Scanner::new(source.to_string());
+    let tokens = scanner.scan_tokens().unwrap().clone();
+    let mut reporter = ErrorReporter::new();
+    reporter.set_color(false);
+    let mut parser = Parser::new(tokens);
+    parser.parse(&mut reporter);
+    parser.errors().iter().map(|err| (err.message.clone(), err.expected.clone())).collect()
+}
+
+#[test]
+fn missing_expression_lists_what_could_start_one() {
+    let errors = parse_errors("print 1 + ;");
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].1.contains(&TokenType::Number));
+    assert!(errors[0].1.contains(&TokenType::Identifier));
+    assert!(errors[0].1.contains(&TokenType::LeftParen));
+    assert!(errors[0].0.starts_with("Expect expression. at ';' (expected number, string, identifier"), "{}", errors[0].0);
+}
+
+#[test]
+fn unclosed_argument_list_suggests_comma_or_paren() {
+    let errors = parse_errors("f(1, 2;");
+    assert_eq!(errors[0].1, [TokenType::Comma, TokenType::RightParen]);
+    assert_eq!(errors[0].0, "Expect ')' after arguments. at ';' (expected ',' or ')')");
+}
+
+#[test]
+fn single_expected_token_is_not_repeated() {
+    let errors = parse_errors("if (true print 1;");
+    assert_eq!(errors[0].1, [TokenType::RightParen]);
+    assert_eq!(errors[0].0, "Expect ')' after if condition. at 'print'");
+}
+
+#[test]
+fn chained_comparison_suggests_and() {
+    let errors = parse_errors("print 1 < 2 < 3;");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, "Chained comparisons are not supported; use 'and'. at '<'");
+
+    // With parentheses it's clearly on purpose, so the parser lets it through
+    assert!(parse_errors("print (1 < 2) < 3;").is_empty());
+}
+
+// Where each parse error points: (line, the source text from its start offset to the end of that token)
+fn parse_error_spans(source: &str) -> Vec<(usize, String)> {
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let mut reporter = ErrorReporter::new();
+    reporter.set_color(false);
+    let mut parser = Parser::new(tokens);
+    parser.parse(&mut reporter);
+    parser.errors().iter().map(|err| (err.line, source[err.start..err.start + err.length].to_string())).collect()
+}
+
+#[test]
+fn error_in_second_statement_on_a_line_points_at_it() {
+    let source = "var a = 1; var b = ;";
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let mut parser = Parser::new(tokens);
+    parser.parse(&mut ErrorReporter::new());
+    assert_eq!(parser.errors().len(), 1);
+    assert_eq!(parser.errors()[0].start, source.rfind(';').unwrap());
+
+    assert_eq!(parse_error_spans("print 1;\nvar a = 1; print a +;"), vec![(2, ";".to_string())]);
+}
+
+#[test]
+fn recovery_resumes_at_the_next_statement_on_the_same_line() {
+    // The missing ';' is reported at "var", and the second declaration is still parsed and checked
+    let errors = parse_error_spans("var a = 1 var b = ;");
+    assert_eq!(errors, vec![(1, "var".to_string()), (1, ";".to_string())]);
+
+    let errors = parse_error_spans("print ); print (; var c = 1;");
+    assert_eq!(errors, vec![(1, ")".to_string()), (1, ";".to_string())]);
+}
+
+#[test]
+fn errors_on_three_statements_in_a_row() {
+    let errors = parse_error_spans("var = 1; print 2 print 3; x = ;");
+    assert_eq!(errors, vec![(1, "=".to_string()), (1, "print".to_string()), (1, ";".to_string())]);
+}
+
+#[test]
+fn runtime_error_shows_the_line_from_the_input_that_defined_it() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("fun f() {\n  return -\"x\";\n}").unwrap();
+    let err = interpreter.eval_str("var x = 1;\nf();").unwrap_err();
+    let err = err.downcast::<RuntimeError>().unwrap();
+
+    let mut reporter = ErrorReporter::new();
+    reporter.set_color(false);
+    reporter.set_source("var x = 1;\nf();");
+    let output = reporter.format_runtime_error(&err);
+    assert_eq!(output, "[line 2] Runtime Error: Operand must be a number.\n2 |   return -\"x\";\n  |          ^");
+}
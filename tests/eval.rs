@@ -0,0 +1,41 @@
+use rlox::{Interpreter, Value};
+
+fn eval(interpreter: &mut Interpreter, source: &str) -> Value {
+    interpreter.eval_str(source).unwrap()
+}
+
+#[test]
+fn eval_returns_the_last_value() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(eval(&mut interpreter, "eval(\"1 + 2\")"), Value::Int(3));
+    assert_eq!(eval(&mut interpreter, "eval(\"var a = 1; a + 1\")"), Value::Int(2));
+    assert_eq!(eval(&mut interpreter, "eval(\"\")"), Value::Nil);
+}
+
+#[test]
+fn eval_sees_the_callers_scope() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var outer = 10;").unwrap();
+    assert_eq!(eval(&mut interpreter, "eval(\"outer * 2\")"), Value::Int(20));
+    assert_eq!(eval(&mut interpreter, "fun f(x) { return eval(\"x + outer\"); } f(5)"), Value::Int(15));
+    // Variables it defines land in the caller's scope
+    assert_eq!(eval(&mut interpreter, "var seen; { var local = 1; eval(\"var made = local + 1;\"); seen = made; } seen"), Value::Int(2));
+    assert!(interpreter.eval_str("made").is_err(), "made belongs to the block");
+}
+
+#[test]
+fn eval_errors_point_at_the_call() {
+    let mut interpreter = Interpreter::new();
+    assert!(interpreter.eval_str("eval(\"1 +\")").is_err());
+    assert!(interpreter.eval_str("eval(\"nil + 1\")").is_err());
+    assert!(interpreter.eval_str("eval(42)").is_err());
+}
+
+#[test]
+fn runaway_eval_recursion_is_an_error() {
+    let mut interpreter = Interpreter::new();
+    let err = interpreter.eval_str("fun f() { return eval(\"f()\"); } f();").unwrap_err();
+    assert!(err.to_string().contains("Too many nested eval() calls."), "{}", err);
+    // Everything unwound, so eval works again afterwards
+    assert_eq!(eval(&mut interpreter, "eval(\"eval(\\\"4\\\")\")"), Value::Int(4));
+}
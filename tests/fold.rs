@@ -0,0 +1,38 @@
+use rlox::ast_printer::AstPrinter;
+use rlox::expr::Expr;
+use rlox::fold::fold_constants;
+use rlox::{Parser, Scanner};
+
+fn folded(source: &str) -> String {
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let expr = Parser::new(tokens).parse_expression().unwrap();
+    AstPrinter::new().print(&fold_constants(expr))
+}
+
+#[test]
+fn literal_arithmetic_is_folded() {
+    assert_eq!(folded("2 + 3 * 4"), "14");
+    assert_eq!(folded("(1 + 2) * -3"), "-9");
+    assert_eq!(folded("10 / 4"), "2.5");
+    assert_eq!(folded("1 < 2 and \"a\" + \"b\" == \"ab\""), "true");
+}
+
+#[test]
+fn subtrees_with_variables_keep_their_shape() {
+    assert_eq!(folded("x + 2 * 3"), "(+ x 6)");
+    assert_eq!(folded("f(1) * (2 + 2)"), "(* (call f 1) 4)");
+}
+
+#[test]
+fn errors_are_not_folded() {
+    assert_eq!(folded("1 / 0"), "(/ 1 0)");
+    assert_eq!(folded("1 + (2 / 0)"), "(+ 1 (group (/ 2 0)))");
+    assert_eq!(folded("-\"a\""), "(- a)");
+}
+
+#[test]
+fn folded_expression_is_a_literal() {
+    let tokens = Scanner::new("2 + 3 * 4".to_string()).scan_tokens().unwrap().clone();
+    let expr = fold_constants(Parser::new(tokens).parse_expression().unwrap());
+    assert!(matches!(expr, Expr::Literal { .. }));
+}
@@ -0,0 +1,59 @@
+use rlox::formatter::format_program;
+use rlox::{ErrorReporter, Parser, Scanner};
+
+fn format(source: &str) -> String {
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let statements = Parser::new(tokens).parse(&mut ErrorReporter::new()).unwrap();
+    format_program(&statements)
+}
+
+#[test]
+fn one_line_program_becomes_indented_source() {
+    let formatted = format("fun add(a,b){var s=a+b*2;if(s>10){print s;}else print -s;return s;}var x=add(1,(2));");
+    let expected = "\
+fun add(a, b) {
+  var s = a + b * 2;
+  if (s > 10) {
+    print s;
+  } else print -s;
+  return s;
+}
+var x = add(1, (2));
+";
+    assert_eq!(formatted, expected);
+}
+
+#[test]
+fn loops_and_collections() {
+    let formatted = format("for(var i=0;i<3;i=i+1){while(i>5)break;}for(;;){}for(x in 1..=3)print x,[1,2],{\"a\":nil};");
+    let expected = "\
+for (var i = 0; i < 3; i = i + 1) {
+  while (i > 5) break;
+}
+for (;;) {}
+for (x in 1..=3) print x, [1, 2], {\"a\": nil};
+";
+    assert_eq!(formatted, expected);
+}
+
+#[test]
+fn literals_read_back_as_the_same_values() {
+    assert_eq!(format(r#"print 2.0, 2, 0.5, "a\n\"b\"", r"C:\dir", true;"#), "print 2.0, 2, 0.5, \"a\\n\\\"b\\\"\", \"C:\\\\dir\", true;\n");
+    assert_eq!(format("print -Infinity, NaN;"), "print -Infinity, NaN;\n");
+}
+
+#[test]
+fn formatting_twice_gives_the_same_text() {
+    let source = "var[a,...rest]=[1,2,3];try{throw a;}catch(e){{print e;}}fun f(){}if(a&&!rest)a=-a;else if(a or nil)a?.b;m[0]=m[1].c(2);var v=while(true){break 1;};";
+    let once = format(source);
+    assert_eq!(format(&once), once);
+}
+
+#[test]
+fn fmt_command_prints_the_formatted_file() {
+    let path = std::env::temp_dir().join("rlox_fmt.lox");
+    std::fs::write(&path, "var x=1;{print x;}").unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rlox")).arg("fmt").arg(&path).output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "var x = 1;\n{\n  print x;\n}\n");
+}
@@ -0,0 +1,44 @@
+use rlox::{Interpreter, Value};
+
+// Closures hold the id of their environment, not a copy of it, so a function sees names
+// declared after it as long as they exist by the time it is called. No hoisting pass needed.
+
+#[test]
+fn function_can_call_one_declared_later() {
+    let mut interpreter = Interpreter::new();
+    let value = interpreter.eval_str("fun a() { return b(); } fun b() { return 1; } a()").unwrap();
+    assert_eq!(value, Value::Int(1));
+}
+
+#[test]
+fn mutual_recursion_at_top_level() {
+    let mut interpreter = Interpreter::new();
+    let source = "
+        fun isEven(n) { if (n == 0) return true; return isOdd(n - 1); }
+        fun isOdd(n) { if (n == 0) return false; return isEven(n - 1); }
+    ";
+    interpreter.eval_str(source).unwrap();
+    assert_eq!(interpreter.eval_str("isEven(10)").unwrap(), Value::Boolean(true));
+    assert_eq!(interpreter.eval_str("isOdd(7)").unwrap(), Value::Boolean(true));
+}
+
+#[test]
+fn mutual_recursion_inside_a_block() {
+    let mut interpreter = Interpreter::new();
+    let source = "
+        var result;
+        {
+            fun ping(n) { if (n <= 0) return \"ping\"; return pong(n - 1); }
+            fun pong(n) { if (n <= 0) return \"pong\"; return ping(n - 1); }
+            result = ping(3);
+        }
+        result
+    ";
+    assert_eq!(interpreter.eval_str(source).unwrap(), Value::String("pong".to_string()));
+}
+
+#[test]
+fn calling_before_the_declaration_runs_is_still_an_error() {
+    let err = Interpreter::new().eval_str("fun a() { return b(); } a(); fun b() { return 1; }").unwrap_err();
+    assert!(err.to_string().contains("Undefined variable 'b'."));
+}
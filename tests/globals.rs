@@ -0,0 +1,49 @@
+use rlox::{Interpreter, Value};
+
+#[test]
+fn a_global_set_from_rust_is_visible_to_the_script() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_global("width", Value::Int(6));
+    interpreter.set_global("label", Value::from("area"));
+    assert_eq!(interpreter.eval_str("label + \": \" + str(width * 7)").unwrap(), Value::from("area: 42"));
+}
+
+#[test]
+fn globals_the_script_defines_can_be_read_back() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var total = 0; for (var i = 1; i <= 4; i = i + 1) total = total + i;").unwrap();
+    assert_eq!(interpreter.get_global("total"), Some(Value::Int(10)));
+    assert_eq!(interpreter.get_global("i"), None, "loop variables aren't globals");
+    assert_eq!(interpreter.get_global("missing"), None);
+}
+
+#[test]
+fn values_go_in_and_out_around_interpret_calls() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_global("input", Value::list(vec![Value::Int(1), Value::Int(2), Value::Int(3)]));
+    interpreter.eval_str("var output = len(input); push(input, 4);").unwrap();
+    assert_eq!(interpreter.get_global("output"), Some(Value::Int(3)));
+
+    // The list is shared, so the script's push shows up on the Rust side too
+    let Some(Value::List(list)) = interpreter.get_global("input") else { panic!("input should still be a list") };
+    assert_eq!(list.borrow().len(), 4);
+}
+
+#[test]
+fn set_global_overwrites_and_reads_as_nil_when_given_nil() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var x = 1;").unwrap();
+    interpreter.set_global("x", Value::Int(2));
+    assert_eq!(interpreter.eval_str("x").unwrap(), Value::Int(2));
+
+    // A nil global still exists, unlike one that was never set
+    interpreter.set_global("x", Value::Nil);
+    assert_eq!(interpreter.get_global("x"), Some(Value::Nil));
+}
+
+#[test]
+fn get_global_ignores_locals_in_scope() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var name = \"global\"; fun f() { var name = \"local\"; return name; } f();").unwrap();
+    assert_eq!(interpreter.get_global("name"), Some(Value::from("global")));
+}
@@ -0,0 +1,55 @@
+use rlox::{Interpreter, Value};
+
+fn eval(source: &str) -> Value {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("fun double(x) { return x * 2; } fun add(a, b) { return a + b; }").unwrap();
+    interpreter.eval_str(source).unwrap()
+}
+
+fn list(values: &[i64]) -> Value {
+    let mut interpreter = Interpreter::new();
+    let source: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    interpreter.eval_str(&format!("[{}]", source.join(", "))).unwrap()
+}
+
+#[test]
+fn map_doubles_each_element() {
+    assert_eq!(eval("map([1, 2, 3], double)"), list(&[2, 4, 6]));
+    assert_eq!(eval("map([], double)"), list(&[]));
+}
+
+#[test]
+fn filter_keeps_truthy_results() {
+    assert_eq!(eval("fun big(x) { return x > 1; } filter([1, 2, 3], big)"), list(&[2, 3]));
+}
+
+#[test]
+fn reduce_sums() {
+    assert_eq!(eval("reduce([1, 2, 3, 4], add, 0)"), Value::Int(10));
+    assert_eq!(eval("reduce([], add, 42)"), Value::Int(42));
+}
+
+#[test]
+fn natives_work_as_callbacks() {
+    assert_eq!(eval(r#"map(["a", "b"], toUpper)"#), eval(r#"["A", "B"]"#));
+    assert_eq!(eval("map([[1], [1, 2]], len)"), list(&[1, 2]));
+}
+
+#[test]
+fn callbacks_can_close_over_variables() {
+    assert_eq!(eval("var n = 10; fun addN(x) { return x + n; } map([1, 2], addN)"), list(&[11, 12]));
+}
+
+#[test]
+fn wrong_callback_arity_is_a_runtime_error() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("fun add(a, b) { return a + b; }").unwrap();
+    let error = interpreter.eval_str("map([1, 2], add)").unwrap_err();
+    assert!(error.to_string().contains("Expected 2 arguments but got 1."), "{}", error);
+
+    let error = interpreter.eval_str("map([1], 5)").unwrap_err();
+    assert!(error.to_string().contains("Can only call functions and classes."), "{}", error);
+
+    let error = interpreter.eval_str("map(5, add)").unwrap_err();
+    assert!(error.to_string().contains("Argument to map() must be a list."), "{}", error);
+}
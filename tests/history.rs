@@ -0,0 +1,58 @@
+use rlox::history::History;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn history_path(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("rlox-history-{}-{}", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn entered_lines_are_appended_to_the_file() {
+    let path = history_path("append");
+    fs::write(&path, "print 1;\n").unwrap();
+
+    let mut history = History::open(&path);
+    history.add("var a = 2;\n").unwrap();
+    history.add("   \n").unwrap(); // blank lines aren't worth keeping
+    history.add("print a;\n").unwrap();
+
+    assert_eq!(history.entries(), ["print 1;", "var a = 2;", "print a;"]);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "print 1;\nvar a = 2;\nprint a;\n");
+    assert_eq!(History::open(&path).entries(), history.entries()); // reloaded on the next start
+}
+
+#[test]
+fn missing_file_is_empty_history() {
+    let history = History::open(history_path("missing"));
+    assert!(history.entries().is_empty());
+}
+
+#[test]
+fn unwritable_file_is_an_error_not_a_panic() {
+    let dir = std::env::temp_dir(); // a directory can't be opened for appending
+    let mut history = History::open(&dir);
+    assert!(history.add("print 1;").is_err());
+    history.disable();
+    assert!(history.add("print 2;").is_ok());
+    assert_eq!(history.entries(), ["print 1;", "print 2;"]);
+}
+
+#[test]
+fn repl_saves_lines_to_the_given_history_file() {
+    let path = history_path("repl");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rlox"))
+        .arg("--repl-history")
+        .arg(&path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"var x = 1;\nprint x;\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(String::from_utf8_lossy(&output.stdout).contains('1'));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "var x = 1;\nprint x;\n");
+}
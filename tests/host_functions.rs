@@ -0,0 +1,42 @@
+use anyhow::anyhow;
+use rlox::{Interpreter, RuntimeError, Value};
+
+fn with_double() -> Interpreter {
+    let mut interpreter = Interpreter::new();
+    interpreter.define_native("double", 1, |_, args| match &args[0] {
+        Value::Int(n) => Ok(Value::Int(n * 2)),
+        Value::Number(n) => Ok(Value::Number(n * 2.0)),
+        _ => Err(anyhow!("double() needs a number.")),
+    });
+    interpreter
+}
+
+#[test]
+fn host_function_is_callable_from_lox() {
+    let mut interpreter = with_double();
+    assert_eq!(interpreter.eval_str("double(21)").unwrap(), Value::Int(42));
+    assert_eq!(interpreter.eval_str("var d = double; d(d(1.5))").unwrap(), Value::Number(6.0));
+    assert_eq!(interpreter.eval_str("double").unwrap().to_string(), "<native fn double>");
+}
+
+#[test]
+fn host_function_errors_point_at_the_call() {
+    let err = with_double().eval_str("\n double(\"x\");").unwrap_err();
+    let err = err.downcast::<RuntimeError>().unwrap();
+    assert_eq!(err.message, "double() needs a number.");
+    assert_eq!(err.token.line, 2);
+
+    let err = with_double().eval_str("double(1, 2);").unwrap_err();
+    assert_eq!(err.downcast::<RuntimeError>().unwrap().message, "Expected 1 arguments but got 2.");
+}
+
+#[test]
+fn host_function_can_call_back_into_the_interpreter() {
+    let mut interpreter = Interpreter::new();
+    interpreter.define_native("evalTwice", 1, |interpreter, args| {
+        let source = args[0].to_string();
+        interpreter.eval_str(&source)?;
+        interpreter.eval_str(&source)
+    });
+    assert_eq!(interpreter.eval_str("var n = 0; evalTwice(\"n = n + 1\")").unwrap(), Value::Int(2));
+}
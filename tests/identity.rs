@@ -0,0 +1,56 @@
+use rlox::{Interpreter, Value};
+
+// There are no classes or instances yet. Functions are the reference values that can look alike
+// while being different objects, so they get the identity semantics asked for instances.
+
+fn eval(interpreter: &mut Interpreter, source: &str) -> Value {
+    interpreter.eval_str(source).unwrap()
+}
+
+#[test]
+fn same_function_through_two_variables_is_equal() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(eval(&mut interpreter, "fun foo() {} var a = foo; var b = a; a == b"), Value::Boolean(true));
+    assert_eq!(eval(&mut interpreter, "foo == foo"), Value::Boolean(true));
+}
+
+#[test]
+fn closures_from_separate_calls_are_not_equal() {
+    let mut interpreter = Interpreter::new();
+    let source = "fun make() { var n = 0; fun get() { return n; } return get; } var a = make(); var b = make();";
+    interpreter.eval_str(source).unwrap();
+    // Same name, same code, but each closes over its own n
+    assert_eq!(eval(&mut interpreter, "a == b"), Value::Boolean(false));
+    assert_eq!(eval(&mut interpreter, "a != b"), Value::Boolean(true));
+}
+
+#[test]
+fn different_declarations_with_the_same_name_are_not_equal() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("fun f() { return 1; } var first = f; fun f() { return 1; }").unwrap();
+    assert_eq!(eval(&mut interpreter, "first == f"), Value::Boolean(false));
+}
+
+#[test]
+fn declarations_at_the_same_spot_in_different_sources_are_not_equal() {
+    // Each eval_str is its own source, so both f's are on line 1 at offset 0, like two REPL lines
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("fun f() { return 1; } var first = f;").unwrap();
+    interpreter.eval_str("fun f() { return 2; }").unwrap();
+    assert_eq!(eval(&mut interpreter, "first == f"), Value::Boolean(false));
+    assert_eq!(eval(&mut interpreter, "first()"), Value::Int(1));
+    assert_eq!(eval(&mut interpreter, "f()"), Value::Int(2));
+}
+
+#[test]
+fn functions_from_an_earlier_snapshot_arent_equal_to_later_ones() {
+    // restore() rolls the arena back, so the next function can get the same closure id again
+    let mut interpreter = Interpreter::new();
+    let snapshot = interpreter.snapshot();
+    let first = eval(&mut interpreter, "fun f() { var x = 1; fun g() { return x; } return g; } f()");
+    interpreter.restore(snapshot);
+    let second = eval(&mut interpreter, "fun f() { var x = 2; fun g() { return x; } return g; } f()");
+    let (Value::Function(a), Value::Function(b)) = (&first, &second) else { panic!("expected two functions") };
+    assert_eq!(a.closure(), b.closure(), "the test needs the closure ids to line up");
+    assert_ne!(first, second);
+}
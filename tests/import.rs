@@ -0,0 +1,103 @@
+use rlox::{Interpreter, Value};
+use std::fs;
+use std::path::PathBuf;
+
+// A fresh directory under the system temp dir for each test's .lox files
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rlox-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn imported_function_can_be_called() {
+    let dir = scratch_dir("import");
+    fs::write(dir.join("helpers.lox"), "fun double(x) { return x * 2; }").unwrap();
+
+    let mut interpreter = Interpreter::new();
+    interpreter.set_script_path(dir.join("main.lox"));
+    let value = interpreter.eval_str(r#"import "helpers.lox"; double(21)"#).unwrap();
+    assert_eq!(value, Value::Int(42));
+}
+
+#[test]
+fn nested_imports_resolve_relative_to_the_importing_file() {
+    let dir = scratch_dir("nested");
+    fs::create_dir_all(dir.join("lib")).unwrap();
+    fs::write(dir.join("lib/a.lox"), r#"import "b.lox"; var a = b + 1;"#).unwrap();
+    fs::write(dir.join("lib/b.lox"), "var b = 1;").unwrap();
+
+    let mut interpreter = Interpreter::new();
+    interpreter.set_script_path(dir.join("main.lox"));
+    assert_eq!(interpreter.eval_str(r#"import "lib/a.lox"; a"#).unwrap(), Value::Int(2));
+}
+
+#[test]
+fn circular_import_is_an_error() {
+    let dir = scratch_dir("circular");
+    fs::write(dir.join("a.lox"), r#"import "b.lox";"#).unwrap();
+    fs::write(dir.join("b.lox"), r#"import "a.lox";"#).unwrap();
+
+    let mut interpreter = Interpreter::new();
+    interpreter.set_script_path(dir.join("main.lox"));
+    let err = interpreter.eval_str(r#"import "a.lox";"#).unwrap_err();
+    assert!(err.to_string().contains("Circular import of"), "{}", err);
+}
+
+#[test]
+fn missing_file_is_a_runtime_error() {
+    let dir = scratch_dir("missing");
+    let mut interpreter = Interpreter::new();
+    interpreter.set_script_path(dir.join("main.lox"));
+    let err = interpreter.eval_str(r#"import "nope.lox";"#).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("Can't import") && message.contains("nope.lox"), "{}", message);
+}
+
+#[test]
+fn modules_keep_same_named_functions_apart() {
+    let dir = scratch_dir("modules");
+    fs::write(dir.join("math.lox"), "fun describe(x) { return x * x; } var name = \"math\";").unwrap();
+    fs::write(dir.join("text.lox"), "fun describe(x) { return \"value: \" + x; }").unwrap();
+
+    let mut interpreter = Interpreter::new();
+    interpreter.set_script_path(dir.join("main.lox"));
+    interpreter.eval_str(r#"import "math.lox" as m; import "text.lox" as t;"#).unwrap();
+
+    assert_eq!(interpreter.eval_str("m.describe(3)").unwrap(), Value::Int(9));
+    assert_eq!(interpreter.eval_str("t.describe(3)").unwrap(), Value::String("value: 3".to_string()));
+    assert_eq!(interpreter.eval_str("m.name").unwrap(), Value::String("math".to_string()));
+    assert_eq!(interpreter.eval_str("m").unwrap().to_string(), "<module m>");
+
+    // Nothing leaks into the importer's globals
+    assert!(interpreter.eval_str("describe;").is_err());
+    assert!(interpreter.eval_str("m.missing;").is_err());
+}
+
+#[test]
+fn circular_module_import_is_an_error() {
+    let dir = scratch_dir("circular-modules");
+    fs::write(dir.join("a.lox"), r#"import "b.lox" as b;"#).unwrap();
+    fs::write(dir.join("b.lox"), r#"import "a.lox" as a;"#).unwrap();
+
+    let mut interpreter = Interpreter::new();
+    interpreter.set_script_path(dir.join("main.lox"));
+    let err = interpreter.eval_str(r#"import "a.lox" as a;"#).unwrap_err();
+    assert!(err.to_string().contains("Circular import of"), "{}", err);
+}
+
+#[test]
+fn runtime_error_in_an_imported_file_shows_that_files_line() {
+    let dir = scratch_dir("error-snippet");
+    fs::write(dir.join("lib.lox"), "var a = 1;\nprint a + nil;\n").unwrap();
+    fs::write(dir.join("main.lox"), "print \"main\";\nimport \"lib.lox\";\n").unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rlox"))
+        .arg(dir.join("main.lox"))
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("[line 2] Runtime Error: Operands must be two numbers or two strings.\n2 | print a + nil;\n  |         ^\n"), "{}", stderr);
+    assert!(!stderr.contains("import \"lib.lox\""), "{}", stderr);
+}
@@ -0,0 +1,34 @@
+use rlox::{Interpreter, Value};
+
+fn eval(source: &str) -> Value {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("fun add(a, b) { return a + b; }").unwrap();
+    interpreter.eval_str(source).unwrap()
+}
+
+#[test]
+fn user_functions() {
+    assert_eq!(eval("isCallable(add)"), Value::Boolean(true));
+    assert_eq!(eval("arity(add)"), Value::Int(2));
+    assert_eq!(eval("fun none() {} arity(none)"), Value::Int(0));
+}
+
+#[test]
+fn natives() {
+    assert_eq!(eval("isCallable(len)"), Value::Boolean(true));
+    assert_eq!(eval("arity(len)"), Value::Int(1));
+    assert_eq!(eval("arity(clock)"), Value::Int(0));
+    assert_eq!(eval("arity(str)"), Value::Nil); // takes any number
+    // The receiver of a string method is already filled in
+    assert_eq!(eval("isCallable(\"abc\".contains)"), Value::Boolean(true));
+    assert_eq!(eval("arity(\"abc\".contains)"), Value::Int(1));
+}
+
+#[test]
+fn non_callables() {
+    assert_eq!(eval("isCallable(1)"), Value::Boolean(false));
+    assert_eq!(eval("isCallable(\"add\")"), Value::Boolean(false));
+    assert_eq!(eval("isCallable(nil)"), Value::Boolean(false));
+    let error = Interpreter::new().eval_str("arity(42)").unwrap_err();
+    assert!(error.to_string().contains("Argument to arity() must be a function."), "{}", error);
+}
@@ -0,0 +1,53 @@
+use rlox::json::{program_to_json, Json};
+use rlox::{ErrorReporter, Parser, Scanner};
+use std::process::Command;
+
+fn to_json(source: &str) -> String {
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let statements = Parser::new(tokens).parse(&mut ErrorReporter::new()).unwrap();
+    program_to_json(&statements).to_string()
+}
+
+#[test]
+fn small_program_as_json() {
+    let json = to_json("var x = 1 + 2.5;\nprint x, nil;");
+    let expected = concat!(
+        r#"[{"type":"Var","name":{"lexeme":"x","line":1,"column":5},"initializer":"#,
+        r#"{"type":"Binary","left":{"type":"Literal","value":1},"operator":{"lexeme":"+","line":1,"column":11},"#,
+        r#""right":{"type":"Literal","value":2.5}}},"#,
+        r#"{"type":"Print","expressions":[{"type":"Variable","name":{"lexeme":"x","line":2,"column":7}},"#,
+        r#"{"type":"Literal","value":null}]}]"#,
+    );
+    assert_eq!(json, expected);
+}
+
+#[test]
+fn nested_statements_and_missing_children() {
+    let json = to_json("fun f(a) { if (a) return; }");
+    let expected = concat!(
+        r#"[{"type":"Function","name":{"lexeme":"f","line":1,"column":5},"params":[{"lexeme":"a","line":1,"column":7}],"#,
+        r#""body":[{"type":"If","condition":{"type":"Variable","name":{"lexeme":"a","line":1,"column":16}},"#,
+        r#""then_branch":{"type":"Return","keyword":{"lexeme":"return","line":1,"column":19},"value":null},"#,
+        r#""else_branch":null}]}]"#,
+    );
+    assert_eq!(json, expected);
+}
+
+#[test]
+fn strings_are_escaped() {
+    let json = Json::String("say \"hi\"\n\\ \u{1}".to_string());
+    assert_eq!(json.to_string(), r#""say \"hi\"\n\\ \u0001""#);
+    assert_eq!(Json::Number(2.0).to_string(), "2.0");
+}
+
+#[test]
+fn emit_json_flag_prints_instead_of_running() {
+    let path = std::env::temp_dir().join("rlox_emit_json.lox");
+    std::fs::write(&path, "print \"hello\";").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_rlox")).arg("--emit-json").arg(&path).output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "[{\"type\":\"Print\",\"expressions\":[{\"type\":\"Literal\",\"value\":\"hello\"}]}]\n"
+    );
+}
@@ -0,0 +1,72 @@
+use rlox::ast_printer::AstPrinter;
+use rlox::stmt::Stmt;
+use rlox::{ErrorReporter, Interpreter, Parser, RuntimeError, Scanner, Value};
+
+fn eval(source: &str) -> Value {
+    Interpreter::new().eval_str(source).unwrap()
+}
+
+#[test]
+fn let_binds_a_value_for_its_body() {
+    assert_eq!(eval("let x = 5 in x * x"), Value::Int(25));
+    assert_eq!(eval("var y = let s = \"ab\" in s + s; y"), Value::String("abab".to_string()));
+}
+
+#[test]
+fn the_name_is_gone_after_the_expression() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(interpreter.eval_str("let x = 5 in x * x").unwrap(), Value::Int(25));
+    let err = interpreter.eval_str("x").unwrap_err().downcast::<RuntimeError>().unwrap();
+    assert_eq!(err.message, "Undefined variable 'x'.");
+}
+
+#[test]
+fn let_shadows_without_touching_the_outer_variable() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var x = 1;").unwrap();
+    // The value is worked out before x is rebound, so it reads the outer x
+    assert_eq!(interpreter.eval_str("let x = x + 10 in x * 2").unwrap(), Value::Int(22));
+    assert_eq!(interpreter.eval_str("x").unwrap(), Value::Int(1));
+}
+
+#[test]
+fn lets_nest() {
+    assert_eq!(eval("let a = 2 in let b = 3 in a * b"), Value::Int(6));
+    assert_eq!(eval("let a = 1 in let a = a + 1 in a"), Value::Int(2));
+    assert_eq!(eval("let a = (let b = 4 in b + 1) in a * 2"), Value::Int(10));
+}
+
+#[test]
+fn closures_made_in_the_body_keep_the_binding() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("fun make(n) { return let k = n * 2 in mul(k); } fun mul(k) { fun f(x) { return x * k; } return f; }").unwrap();
+    assert_eq!(interpreter.eval_str("make(3)(5)").unwrap(), Value::Int(30));
+}
+
+#[test]
+fn the_scope_is_restored_after_an_error() {
+    let mut interpreter = Interpreter::new();
+    let before = interpreter.current_env();
+    assert!(interpreter.eval_str("let x = 1 in x + nil").is_err());
+    assert_eq!(interpreter.current_env(), before);
+}
+
+fn parse(source: &str) -> (Option<Vec<Stmt>>, Vec<String>) {
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let mut parser = Parser::new(tokens);
+    let statements = parser.parse(&mut ErrorReporter::new());
+    (statements, parser.errors().iter().map(|err| err.message.clone()).collect())
+}
+
+#[test]
+fn the_body_reaches_as_far_as_it_can() {
+    let (statements, _) = parse("let x = 1 in x + 1;");
+    let Some(Stmt::Expression { expression }) = statements.unwrap().into_iter().next() else { panic!("expected an expression") };
+    assert_eq!(AstPrinter::new().print(&expression), "(let x 1 (+ x 1))");
+}
+
+#[test]
+fn let_needs_a_name_and_in() {
+    assert_eq!(parse("print let x = 1 x;").1, ["Expect 'in' after let value. at 'x'"]);
+    assert_eq!(parse("print let = 1 in 2;").1, ["Expect variable name after 'let'. at '='"]);
+}
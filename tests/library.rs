@@ -0,0 +1,49 @@
+// Uses rlox as a library: scanner -> parser -> interpreter without going through the CLI
+
+use rlox::ast_printer::AstPrinter;
+use rlox::parser::ParseError;
+use rlox::{ErrorReporter, Interpreter, Parser, Scanner, Value};
+
+#[test]
+fn evaluates_expression_through_the_pipeline() {
+    let mut scanner = Scanner::new("var x = (1 + 2) * 4;".to_string());
+    let tokens = scanner.scan_tokens().unwrap().clone();
+
+    let mut error_reporter = ErrorReporter::new();
+    let statements = Parser::new(tokens).parse(&mut error_reporter).unwrap();
+    assert!(!error_reporter.had_error());
+
+    let mut interpreter = Interpreter::new();
+    interpreter.interpret(&statements).unwrap();
+    assert_eq!(interpreter.eval_str("x").unwrap(), Value::Number(12.0));
+}
+
+#[test]
+fn eval_str_returns_final_expression_value() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(interpreter.eval_str("1 + 2").unwrap(), Value::Number(3.0));
+    assert_eq!(interpreter.eval_str("print 1;").unwrap(), Value::Nil);
+}
+
+#[test]
+fn eval_str_reports_syntax_errors() {
+    let mut interpreter = Interpreter::new();
+    assert!(interpreter.eval_str("1 +").is_err());
+}
+
+fn parser_for(source: &str) -> Parser {
+    Parser::new(Scanner::new(source.to_string()).scan_tokens().unwrap().clone())
+}
+
+#[test]
+fn parse_expression_parses_one_expression() {
+    let expr = parser_for("1 + 2 * 3").parse_expression().unwrap();
+    assert_eq!(AstPrinter::new().print(&expr), "(+ 1 (* 2 3))");
+}
+
+#[test]
+fn parse_expression_rejects_trailing_tokens() {
+    let err = parser_for("1 + 2; 3").parse_expression().unwrap_err();
+    let err = err.downcast::<ParseError>().unwrap();
+    assert_eq!(err.message, "Expect end of expression. at ';'");
+}
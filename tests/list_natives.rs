@@ -0,0 +1,56 @@
+use rlox::{Interpreter, RuntimeError, Value};
+
+fn eval(interpreter: &mut Interpreter, source: &str) -> Value {
+    interpreter.eval_str(source).unwrap()
+}
+
+fn error_message(source: &str) -> String {
+    let err = Interpreter::new().eval_str(source).unwrap_err();
+    err.downcast::<RuntimeError>().unwrap().message
+}
+
+fn ints(values: &[i64]) -> Value {
+    Value::list(values.iter().map(|&v| Value::Int(v)).collect())
+}
+
+#[test]
+fn push_appends_in_place() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var a = [1]; var alias = a; push(a, 2); push(alias, 3);").unwrap();
+    assert_eq!(eval(&mut interpreter, "a"), ints(&[1, 2, 3]));
+    assert_eq!(eval(&mut interpreter, "push([], 1)"), Value::Nil);
+}
+
+#[test]
+fn pop_returns_the_last_element() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var a = [1, 2, 3];").unwrap();
+    assert_eq!(eval(&mut interpreter, "pop(a)"), Value::Int(3));
+    assert_eq!(eval(&mut interpreter, "a"), ints(&[1, 2]));
+    assert_eq!(error_message("pop([]);"), "Can't pop from an empty list.");
+}
+
+#[test]
+fn insert_at_any_index_up_to_the_length() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var a = [1, 3]; insert(a, 1, 2); insert(a, 0, 0); insert(a, len(a), 4);").unwrap();
+    assert_eq!(eval(&mut interpreter, "a"), ints(&[0, 1, 2, 3, 4]));
+    assert_eq!(error_message("insert([1], 2, 0);"), "List index 2 is out of range for length 1.");
+    assert_eq!(error_message("insert([1], -1, 0);"), "List index -1 is out of range for length 1.");
+}
+
+#[test]
+fn remove_returns_the_element_at_an_index() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var a = [1, 2, 3];").unwrap();
+    assert_eq!(eval(&mut interpreter, "remove(a, 1)"), Value::Int(2));
+    assert_eq!(eval(&mut interpreter, "a"), ints(&[1, 3]));
+    assert_eq!(error_message("remove([1, 2], 2);"), "List index 2 is out of range for length 2.");
+    assert_eq!(error_message("remove([], 0);"), "List index 0 is out of range for length 0.");
+}
+
+#[test]
+fn mutators_need_a_list() {
+    assert_eq!(error_message("push(\"abc\", 1);"), "Argument to push() must be a list.");
+    assert_eq!(error_message("remove([1], 0.5);"), "Argument to remove() must be an integer.");
+}
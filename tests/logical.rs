@@ -0,0 +1,63 @@
+use rlox::{Interpreter, Value};
+
+fn eval(source: &str) -> Value {
+    Interpreter::new().eval_str(source).unwrap()
+}
+
+fn string(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+#[test]
+fn or_returns_the_first_truthy_operand() {
+    assert_eq!(eval("nil or \"a\""), string("a"));
+    assert_eq!(eval("false or \"a\""), string("a"));
+    assert_eq!(eval("0 or \"a\""), Value::Int(0));
+    assert_eq!(eval("\"\" or \"a\""), string(""));
+    assert_eq!(eval("nil or false"), Value::Boolean(false));
+}
+
+#[test]
+fn and_returns_the_first_falsy_operand() {
+    assert_eq!(eval("\"\" and \"b\""), string("b"));
+    assert_eq!(eval("0 and \"b\""), string("b"));
+    assert_eq!(eval("nil and \"b\""), Value::Nil);
+    assert_eq!(eval("false and nil"), Value::Boolean(false));
+    assert_eq!(eval("1 and 2 and 3"), Value::Int(3));
+}
+
+#[test]
+fn short_circuit_skips_the_right_side() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var calls = 0; fun hit() { calls = calls + 1; return true; }").unwrap();
+
+    assert_eq!(interpreter.eval_str("true or hit()").unwrap(), Value::Boolean(true));
+    assert_eq!(interpreter.eval_str("nil and hit()").unwrap(), Value::Nil);
+    assert_eq!(interpreter.eval_str("calls").unwrap(), Value::Int(0));
+
+    // ...and evaluates it exactly once otherwise
+    assert_eq!(interpreter.eval_str("false or hit()").unwrap(), Value::Boolean(true));
+    assert_eq!(interpreter.eval_str("1 and hit()").unwrap(), Value::Boolean(true));
+    assert_eq!(interpreter.eval_str("calls").unwrap(), Value::Int(2));
+}
+
+#[test]
+fn symbol_forms_match_the_keywords() {
+    for (symbols, keywords) in [
+        ("true && false", "true and false"),
+        ("true || false", "true or false"),
+        ("nil || \"a\"", "nil or \"a\""),
+        ("1 && 2 && 3", "1 and 2 and 3"),
+        ("false || true && false", "false or true and false"),
+    ] {
+        assert_eq!(eval(symbols), eval(keywords), "{}", symbols);
+    }
+    // Both spellings can be mixed
+    assert_eq!(eval("true and false || true"), Value::Boolean(true));
+}
+
+#[test]
+fn single_ampersand_or_pipe_is_an_error() {
+    assert!(Interpreter::new().eval_str("true & false").is_err());
+    assert!(Interpreter::new().eval_str("true | false").is_err());
+}
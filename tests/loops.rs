@@ -0,0 +1,125 @@
+use rlox::{Interpreter, RuntimeError, Value};
+use std::process::Command;
+
+#[test]
+fn while_expression_takes_value_of_break() {
+    let mut interpreter = Interpreter::new();
+    let value = interpreter.eval_str("var x = while (true) { break 42; }; x").unwrap();
+    assert_eq!(value, Value::Int(42));
+}
+
+#[test]
+fn while_expression_without_break_is_nil() {
+    let mut interpreter = Interpreter::new();
+    let value = interpreter.eval_str("var i = 0; var x = while (i < 3) { i = i + 1; }; x").unwrap();
+    assert_eq!(value, Value::Nil);
+}
+
+#[test]
+fn break_only_leaves_innermost_loop() {
+    let mut interpreter = Interpreter::new();
+    let source = "
+        var count = 0;
+        for (var i = 0; i < 3; i = i + 1) {
+            while (true) { break; }
+            count = count + 1;
+        }
+        count";
+    assert_eq!(interpreter.eval_str(source).unwrap(), Value::Int(3));
+}
+
+#[test]
+fn break_outside_loop_is_a_parse_error() {
+    let mut interpreter = Interpreter::new();
+    assert!(interpreter.eval_str("break;").is_err());
+    assert!(interpreter.eval_str("while (true) { fun f() { break; } }").is_err());
+}
+
+#[test]
+fn infinite_loop_hits_the_iteration_limit() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_max_iterations(100);
+    let err = interpreter.eval_str("while (true) {}").unwrap_err();
+    let err = err.downcast::<RuntimeError>().unwrap();
+    assert_eq!(err.message, "Loop iteration limit exceeded.");
+    assert_eq!(err.token.lexeme, "while");
+}
+
+#[test]
+fn iteration_limit_counts_every_kind_of_loop() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_max_iterations(10);
+    // 4 + 4 iterations fit, the for-in below pushes the total past 10
+    interpreter.eval_str("for (var i = 0; i < 4; i = i + 1) {} var j = 0; var x = while (j < 4) { j = j + 1; };").unwrap();
+    let err = interpreter.eval_str("for (k in 0..5) {}").unwrap_err();
+    assert!(err.to_string().contains("Loop iteration limit exceeded."));
+}
+
+#[test]
+fn no_iteration_limit_by_default() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(interpreter.eval_str("var i = 0; while (i < 100000) i = i + 1; i").unwrap(), Value::Int(100000));
+}
+
+#[test]
+fn max_iterations_flag_stops_the_script() {
+    let path = std::env::temp_dir().join("rlox_max_iterations.lox");
+    std::fs::write(&path, "var i = 0;
+while (true) i = i + 1;
+").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rlox"))
+        .args(["--max-iterations", "50"])
+        .arg(&path)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(70));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("[line 2] Runtime Error: Loop iteration limit exceeded."));
+}
+
+#[test]
+fn continue_in_a_for_loop_still_runs_the_increment() {
+    let mut interpreter = Interpreter::new();
+    let source = r#"
+        var out = "";
+        for (var i = 0; i < 6; i = i + 1) {
+            if (i == 1 or i == 4) continue;
+            out = out + str(i);
+        }
+        out
+    "#;
+    assert_eq!(interpreter.eval_str(source).unwrap(), Value::String("0235".to_string()));
+}
+
+#[test]
+fn continue_in_while_and_for_in() {
+    let mut interpreter = Interpreter::new();
+    let source = "var i = 0; var sum = 0; while (i < 5) { i = i + 1; if (i == 3) continue; sum = sum + i; } sum";
+    assert_eq!(interpreter.eval_str(source).unwrap(), Value::Int(12));
+
+    let source = "var big = 0; for (n in 0..10) { if (n < 7) continue; big = big + n; } big";
+    assert_eq!(interpreter.eval_str(source).unwrap(), Value::Int(24));
+}
+
+#[test]
+fn continue_only_affects_the_innermost_loop() {
+    let mut interpreter = Interpreter::new();
+    let source = "
+        var pairs = 0;
+        for (var i = 0; i < 3; i = i + 1) {
+            for (var j = 0; j < 3; j = j + 1) {
+                if (j == i) continue;
+                pairs = pairs + 1;
+            }
+        }
+        pairs
+    ";
+    assert_eq!(interpreter.eval_str(source).unwrap(), Value::Int(6));
+}
+
+#[test]
+fn continue_outside_a_loop_is_a_parse_error() {
+    assert!(Interpreter::new().eval_str("continue;").is_err());
+    assert!(Interpreter::new().eval_str("while (true) { fun f() { continue; } }").is_err());
+}
@@ -0,0 +1,86 @@
+use rlox::{Interpreter, RuntimeError, Value};
+
+fn eval(source: &str) -> Value {
+    Interpreter::new().eval_str(source).unwrap()
+}
+
+fn error_message(source: &str) -> String {
+    let err = Interpreter::new().eval_str(source).unwrap_err();
+    err.downcast::<RuntimeError>().unwrap().message
+}
+
+#[test]
+fn format_rounds_to_fixed_decimals() {
+    assert_eq!(eval("format(3.14159, 2)"), Value::String("3.14".to_string()));
+    assert_eq!(eval("format(1.96, 1)"), Value::String("2.0".to_string()));
+    assert_eq!(eval("format(2.5, 0)"), Value::String("2".to_string()));
+    assert_eq!(eval("format(5, 2)"), Value::String("5.00".to_string()));
+    assert_eq!(eval("format(-0.126, 2)"), Value::String("-0.13".to_string()));
+}
+
+#[test]
+fn format_checks_its_arguments() {
+    assert_eq!(error_message("format(\"x\", 2);"), "Argument to format() must be a number.");
+    assert_eq!(error_message("format(1.5, 0.5);"), "Argument to format() must be an integer.");
+    assert_eq!(error_message("format(1.5, -1);"), "Number of decimals for format() can't be negative.");
+}
+
+#[test]
+fn hex_of_integers() {
+    assert_eq!(eval("hex(255)"), Value::String("ff".to_string()));
+    assert_eq!(eval("hex(0)"), Value::String("0".to_string()));
+    assert_eq!(eval("hex(-16)"), Value::String("-10".to_string()));
+    assert_eq!(eval("hex(4096.0)"), Value::String("1000".to_string()));
+}
+
+#[test]
+fn hex_rejects_fractions() {
+    assert_eq!(error_message("hex(1.5);"), "Argument to hex() must be an integer.");
+}
+
+#[test]
+fn approx_eq_within_tolerance() {
+    assert_eq!(eval("0.1 + 0.2 == 0.3"), Value::Boolean(false));
+    assert_eq!(eval("approxEq(0.1 + 0.2, 0.3, 0.0001)"), Value::Boolean(true));
+    assert_eq!(eval("approxEq(1, 1.5, 0.5)"), Value::Boolean(true)); // the bound is inclusive
+}
+
+#[test]
+fn approx_eq_outside_tolerance() {
+    assert_eq!(eval("approxEq(1.0, 1.1, 0.01)"), Value::Boolean(false));
+    assert_eq!(eval("approxEq(-1, 1, 1)"), Value::Boolean(false));
+}
+
+#[test]
+fn approx_eq_wants_numbers() {
+    assert_eq!(error_message("approxEq(1, \"1\", 0.1);"), "Argument to approxEq() must be a number.");
+    assert_eq!(error_message("approxEq(1, 1, nil);"), "Argument to approxEq() must be a number.");
+}
+
+fn grouped(source: &str) -> String {
+    match eval(source) {
+        Value::String(s) => s,
+        other => panic!("grouped returned {}", other),
+    }
+}
+
+#[test]
+fn grouped_adds_thousands_separators() {
+    assert_eq!(grouped("grouped(1234567)"), "1,234,567");
+    assert_eq!(grouped("grouped(123456)"), "123,456");
+    assert_eq!(grouped("grouped(999)"), "999");
+    assert_eq!(grouped("grouped(0)"), "0");
+}
+
+#[test]
+fn grouped_negative_and_fractional() {
+    assert_eq!(grouped("grouped(-1234567)"), "-1,234,567");
+    assert_eq!(grouped("grouped(-12)"), "-12");
+    assert_eq!(grouped("grouped(1234.5678)"), "1,234.5678");
+    assert_eq!(grouped("grouped(-9876543.25)"), "-9,876,543.25");
+}
+
+#[test]
+fn grouped_needs_a_number() {
+    assert_eq!(error_message("grouped(\"1000\")"), "Argument to grouped() must be a number.");
+}
@@ -0,0 +1,146 @@
+use rlox::{ErrorReporter, Interpreter, Parser, Scanner, Value};
+
+fn eval(source: &str) -> Value {
+    Interpreter::new().eval_str(source).unwrap()
+}
+
+#[test]
+fn integer_literals_stay_integers() {
+    assert!(matches!(eval("1 + 2"), Value::Int(3)));
+    assert!(matches!(eval("2 * 3 - 10"), Value::Int(-4)));
+    assert!(matches!(eval("-7"), Value::Int(-7)));
+    assert!(matches!(eval("1.5"), Value::Number(n) if n == 1.5));
+}
+
+#[test]
+fn mixed_arithmetic_promotes_to_float() {
+    assert!(matches!(eval("1 + 2.5"), Value::Number(n) if n == 3.5));
+    assert!(matches!(eval("2.0 * 3"), Value::Number(n) if n == 6.0));
+}
+
+#[test]
+fn overflow_promotes_to_float() {
+    assert!(matches!(eval("9223372036854775807 + 1"), Value::Number(_)));
+}
+
+#[test]
+fn division_always_gives_float() {
+    assert!(matches!(eval("5 / 2"), Value::Number(n) if n == 2.5));
+    assert!(matches!(eval("4 / 2"), Value::Number(n) if n == 2.0));
+}
+
+#[test]
+fn ints_and_floats_compare_by_value() {
+    assert_eq!(eval("1 == 1.0"), Value::Boolean(true));
+    assert_eq!(eval("1 != 1.5"), Value::Boolean(true));
+    assert_eq!(eval("2 < 2.5"), Value::Boolean(true));
+    assert_eq!(eval("1 + 2").to_string(), "3");
+}
+
+#[test]
+fn radix_literals() {
+    assert!(matches!(eval("0xFF"), Value::Int(255)));
+    assert!(matches!(eval("0o17"), Value::Int(15)));
+    assert!(matches!(eval("0b1010"), Value::Int(10)));
+    assert!(matches!(eval("0x10 + 0b1"), Value::Int(17)));
+    assert!(matches!(eval("0"), Value::Int(0)));
+}
+
+#[test]
+fn invalid_radix_digits_are_errors() {
+    let err = Interpreter::new().eval_str("0xG1").unwrap_err();
+    assert!(err.to_string().contains("Invalid digit 'G' in hexadecimal literal '0xG1'"), "{}", err);
+    assert!(Interpreter::new().eval_str("0b102").is_err());
+    assert!(Interpreter::new().eval_str("0o8").is_err());
+    assert!(Interpreter::new().eval_str("0x").is_err());
+}
+
+#[test]
+fn exponent_is_right_associative() {
+    assert!(matches!(eval("2 ** 3 ** 2"), Value::Int(512)));
+    assert!(matches!(eval("(2 ** 3) ** 2"), Value::Int(64)));
+}
+
+#[test]
+fn exponent_precedence() {
+    // Binds tighter than * but looser than unary minus
+    assert!(matches!(eval("3 * 2 ** 2"), Value::Int(12)));
+    assert!(matches!(eval("-2 ** 2"), Value::Int(4)));
+    assert!(matches!(eval("-(2 ** 2)"), Value::Int(-4)));
+}
+
+#[test]
+fn exponent_with_floats_and_negative_powers() {
+    assert!(matches!(eval("2 ** -1"), Value::Number(n) if n == 0.5));
+    assert!(matches!(eval("4 ** 0.5"), Value::Number(n) if n == 2.0));
+    assert!(matches!(eval("2 ** 64"), Value::Number(n) if n == 2f64.powi(64)));
+}
+
+#[test]
+fn infinity_and_nan_literals() {
+    assert_eq!(eval("Infinity"), Value::Number(f64::INFINITY));
+    assert_eq!(eval("-Infinity"), Value::Number(f64::NEG_INFINITY));
+    assert!(matches!(eval("NaN"), Value::Number(n) if n.is_nan()));
+    assert!(Interpreter::new().eval_str("Infinity = 1;").is_err());
+}
+
+#[test]
+fn infinity_and_nan_follow_ieee() {
+    assert_eq!(eval("NaN == NaN"), Value::Boolean(false));
+    assert_eq!(eval("NaN != NaN"), Value::Boolean(true));
+    assert_eq!(eval("NaN < 1 or NaN >= 1"), Value::Boolean(false));
+    assert_eq!(eval("Infinity > 99999999999999999999999999"), Value::Boolean(true));
+    assert_eq!(eval("Infinity + 1 == Infinity"), Value::Boolean(true));
+    assert_eq!(eval("-Infinity < -1000000"), Value::Boolean(true));
+    assert!(matches!(eval("Infinity - Infinity"), Value::Number(n) if n.is_nan()));
+}
+
+#[test]
+fn infinity_and_nan_print_by_name() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(interpreter.eval_str("str(Infinity, \" \", -Infinity, \" \", NaN)").unwrap(),
+               Value::String("Infinity -Infinity NaN".to_string()));
+    assert_eq!(eval("Infinity").to_string(), "Infinity");
+}
+
+#[test]
+fn whole_floats_print_without_a_cast() {
+    assert_eq!(eval("-0.0").to_string(), "-0");
+    assert_eq!(eval("0.0 * -1").to_string(), "-0");
+    assert_eq!(eval("0.0").to_string(), "0");
+    assert_eq!(eval("1000000000000000000.0").to_string(), "1000000000000000000");
+    assert_eq!(eval("123456789012345.0").to_string(), "123456789012345");
+    // Too big for an i64, which used to print as 9223372036854775807
+    assert_eq!(eval("100000000000000000000").to_string(), "100000000000000000000");
+    assert_eq!(eval("-100000000000000000000").to_string(), "-100000000000000000000");
+}
+
+#[test]
+fn tiny_numbers_print_in_full() {
+    assert_eq!(eval("0.000001").to_string(), "0.000001");
+    assert_eq!(eval("-0.5").to_string(), "-0.5");
+}
+
+fn parse_errors(source: &str) -> Vec<String> {
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let mut parser = Parser::new(tokens);
+    parser.parse(&mut ErrorReporter::new());
+    parser.errors().iter().map(|err| err.message.clone()).collect()
+}
+
+#[test]
+fn infinity_and_nan_cant_be_declared() {
+    assert_eq!(parse_errors("var Infinity = 1;"), ["Can't use 'Infinity' as a name, it's a number. at 'Infinity'"]);
+    assert_eq!(parse_errors("var NaN;"), ["Can't use 'NaN' as a name, it's a number. at 'NaN'"]);
+    assert_eq!(parse_errors("fun NaN() {}").len(), 1);
+    assert_eq!(parse_errors("fun f(Infinity) {}").len(), 1);
+    assert_eq!(parse_errors("for (NaN in [1]) {}").len(), 1);
+    assert_eq!(parse_errors("var [a, Infinity] = [1, 2];").len(), 1);
+    assert_eq!(parse_errors("print let Infinity = 1 in 2;").len(), 1);
+    assert_eq!(parse_errors("Infinity = 1;"), ["Invalid assignment target. at '='"]);
+}
+
+#[test]
+fn names_that_only_start_like_infinity_are_fine() {
+    assert_eq!(eval("var Infinite = 1; var NaNa = 2; Infinite + NaNa"), Value::Int(3));
+}
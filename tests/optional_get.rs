@@ -0,0 +1,37 @@
+use rlox::ast_printer::AstPrinter;
+use rlox::{Interpreter, Parser, Scanner, Value};
+use std::fs;
+
+#[test]
+fn optional_get_on_nil_is_nil() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(interpreter.eval_str("nil?.x").unwrap(), Value::Nil);
+    assert_eq!(interpreter.eval_str("var a; a?.upper").unwrap(), Value::Nil);
+}
+
+#[test]
+fn optional_get_on_a_value_acts_like_dot() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(interpreter.eval_str(r#""abc"?.length()"#).unwrap(), Value::Int(3));
+
+    // Classes don't exist yet, so a module stands in for an object with fields
+    let dir = std::env::temp_dir().join(format!("rlox-optional-get-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("point.lox"), "var field = 7;").unwrap();
+    interpreter.set_script_path(dir.join("main.lox"));
+    interpreter.eval_str(r#"import "point.lox" as obj;"#).unwrap();
+    assert_eq!(interpreter.eval_str("obj?.field").unwrap(), Value::Int(7));
+}
+
+#[test]
+fn optional_get_still_errors_on_other_values() {
+    let err = Interpreter::new().eval_str("var n = 1; n?.x;").unwrap_err();
+    assert!(err.to_string().contains("Only modules and strings have properties."));
+}
+
+#[test]
+fn optional_get_parses_in_call_chains() {
+    let tokens = Scanner::new("a?.b.c(1)".to_string()).scan_tokens().unwrap().clone();
+    let expr = Parser::new(tokens).parse_expression().unwrap();
+    assert_eq!(AstPrinter::new().print(&expr), "(call (. (?. a b) c) 1)");
+}
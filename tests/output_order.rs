@@ -0,0 +1,39 @@
+use std::fs::{self, File};
+use std::process::{Command, Stdio};
+
+// Runs a script with stdout and stderr going to the same file, like `rlox script.lox > out.txt 2>&1`
+fn combined_output(name: &str, source: &str) -> String {
+    let dir = std::env::temp_dir();
+    let script = dir.join(format!("{}.lox", name));
+    let capture = dir.join(format!("{}.out", name));
+    fs::write(&script, source).unwrap();
+
+    let file = File::create(&capture).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_rlox"))
+        .arg(&script)
+        .env("NO_COLOR", "1")
+        .stdout(Stdio::from(file.try_clone().unwrap()))
+        .stderr(Stdio::from(file))
+        .status()
+        .unwrap();
+    fs::read_to_string(&capture).unwrap()
+}
+
+#[test]
+fn printed_output_comes_before_the_runtime_error() {
+    let output = combined_output("rlox_output_order", "print \"first\";\nprint \"second\";\nprint 1 + nil;\n");
+    let first = output.find("first").expect(&output);
+    let second = output.find("second").expect(&output);
+    let error = output.find("Operands").expect(&output);
+    assert!(first < second && second < error, "{}", output);
+}
+
+#[test]
+fn output_from_a_loop_is_all_there_before_the_error() {
+    let source = "for (var i = 0; i < 100; i = i + 1) print i;\nundefinedVariable;\n";
+    let output = combined_output("rlox_output_order_loop", source);
+    let error = output.find("Undefined variable").expect(&output);
+    let printed: Vec<&str> = output[..error].lines().take(100).collect();
+    let expected: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+    assert_eq!(printed, expected);
+}
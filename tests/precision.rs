@@ -0,0 +1,25 @@
+use rlox::{Interpreter, Value};
+
+#[test]
+fn numbers_print_shortest_round_trip_by_default() {
+    let mut interpreter = Interpreter::new();
+    let sum = interpreter.eval_str("0.1 + 0.2").unwrap();
+    assert_eq!(interpreter.stringify(&sum), "0.30000000000000004");
+    assert_eq!(interpreter.stringify(&Value::Number(2.5)), "2.5");
+    assert_eq!(interpreter.stringify(&Value::Number(3.0)), "3");
+}
+
+#[test]
+fn fixed_precision_rounds_non_integers() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_precision(Some(2));
+    let sum = interpreter.eval_str("0.1 + 0.2").unwrap();
+    assert_eq!(interpreter.stringify(&sum), "0.30");
+    assert_eq!(interpreter.stringify(&Value::Number(3.0)), "3");
+    assert_eq!(interpreter.stringify(&Value::Int(7)), "7");
+
+    // Applies inside lists and to string concatenation too
+    let list = interpreter.eval_str("[1 / 3, 2]").unwrap();
+    assert_eq!(interpreter.stringify(&list), "[0.33, 2]");
+    assert_eq!(interpreter.eval_str("\"x = \" + 2 / 3").unwrap(), Value::String("x = 0.67".to_string()));
+}
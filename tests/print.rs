@@ -0,0 +1,39 @@
+use rlox::ast_printer::AstPrinter;
+use rlox::{ErrorReporter, Parser, Scanner};
+use std::process::Command;
+
+// Run a script with the rlox binary and return its stdout
+fn run(name: &str, source: &str) -> String {
+    let path = std::env::temp_dir().join(format!("rlox_print_{}.lox", name));
+    std::fs::write(&path, source).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_rlox")).arg(&path).env("NO_COLOR", "1").output().unwrap();
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn print_several_values_separated_by_spaces() {
+    assert_eq!(run("several", "print 1, \"x\", true;"), "1 x true\n");
+    assert_eq!(run("one", "print nil;"), "nil\n");
+}
+
+#[test]
+fn each_print_item_is_a_full_expression() {
+    assert_eq!(run("expressions", "var a = 1; print a = 2, a + 1, [a, 3];"), "2 3 [2, 3]\n");
+}
+
+#[test]
+fn print_list_parses_into_one_statement() {
+    let tokens = Scanner::new("print 1 + 2, f(3, 4);".to_string()).scan_tokens().unwrap().clone();
+    let mut parser = Parser::new(tokens);
+    let statements = parser.parse(&mut ErrorReporter::new()).unwrap();
+    assert_eq!(statements.len(), 1);
+    assert_eq!(AstPrinter::new().print_stmt(&statements[0]), "print (+ 1 2), (call f 3 4)");
+}
+
+#[test]
+fn trailing_comma_in_print_is_an_error() {
+    let tokens = Scanner::new("print 1, ;".to_string()).scan_tokens().unwrap().clone();
+    let mut parser = Parser::new(tokens);
+    parser.parse(&mut ErrorReporter::new());
+    assert!(parser.errors()[0].message.starts_with("Expect expression."));
+}
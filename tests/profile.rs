@@ -0,0 +1,21 @@
+use rlox::Interpreter;
+
+#[test]
+fn profile_counts_calls() {
+    let mut interpreter = Interpreter::new();
+    interpreter.enable_profiling();
+    // fib(5) makes 15 calls in total
+    interpreter.eval_str("fun fib(n) { if (n <= 1) return n; return fib(n - 2) + fib(n - 1); } fib(5);").unwrap();
+
+    let profile = interpreter.profile();
+    assert_eq!(profile.get("call_expr"), Some(&15));
+    assert_eq!(profile.get("function_stmt"), Some(&1));
+    assert_eq!(profile.get("return_stmt"), Some(&15));
+}
+
+#[test]
+fn profile_is_empty_when_disabled() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var a = 1 + 2; print a;").unwrap();
+    assert!(interpreter.profile().is_empty());
+}
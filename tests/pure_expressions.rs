@@ -0,0 +1,31 @@
+use rlox::{Interpreter, Value};
+
+#[test]
+fn pure_expression_statements_are_skipped() {
+    let mut interpreter = Interpreter::new();
+    interpreter.enable_profiling();
+    // eval_str() still evaluates the final "nil", since its value is the result
+    interpreter.eval_str("1 + 2 * 3; -(4); !true == false; [1, 2]; nil").unwrap();
+    assert_eq!(interpreter.profile().get("binary_expr"), None);
+    assert_eq!(interpreter.profile().get("unary_expr"), None);
+    assert_eq!(interpreter.profile().get("list_expr"), None);
+    assert_eq!(interpreter.profile().get("literal_expr"), Some(&1));
+}
+
+#[test]
+fn calls_and_assignments_are_never_skipped() {
+    let mut interpreter = Interpreter::new();
+    let result = interpreter
+        .eval_str("var count = 0; fun bump() { count = count + 1; } bump(); count = count + 10; bump(); count")
+        .unwrap();
+    assert_eq!(result, Value::Int(12));
+}
+
+#[test]
+fn expressions_that_can_fail_still_fail() {
+    let mut interpreter = Interpreter::new();
+    assert!(interpreter.eval_str("1 + nil; 0;").is_err());
+    assert!(interpreter.eval_str("1 / 0; 0;").is_err());
+    assert!(interpreter.eval_str("-\"a\"; 0;").is_err());
+    assert!(interpreter.eval_str("undefinedName; 0;").is_err());
+}
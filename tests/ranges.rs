@@ -0,0 +1,57 @@
+use rlox::{Interpreter, Value};
+
+fn eval(source: &str) -> Value {
+    Interpreter::new().eval_str(source).unwrap()
+}
+
+#[test]
+fn exclusive_range_leaves_out_the_end() {
+    assert_eq!(eval("var count = 0; for (n in 1..3) count = count + 1; count"), Value::Int(2));
+    assert_eq!(eval("var sum = 0; for (n in 1..3) sum = sum * 10 + n; sum"), Value::Int(12));
+}
+
+#[test]
+fn inclusive_range_includes_the_end() {
+    assert_eq!(eval("var sum = 0; for (n in 1..=3) sum = sum * 10 + n; sum"), Value::Int(123));
+}
+
+#[test]
+fn range_bounds_can_be_expressions() {
+    assert_eq!(eval("var n = 4; var sum = 0; for (i in 0..n - 1) sum = sum + i; sum"), Value::Int(3));
+}
+
+#[test]
+fn len_of_ranges_and_collections() {
+    assert_eq!(eval("len(1..3)"), Value::Int(2));
+    assert_eq!(eval("len(1..=3)"), Value::Int(3));
+    assert_eq!(eval("len(5..1)"), Value::Int(0));
+    assert_eq!(eval("len([1, 2, 3, 4])"), Value::Int(4));
+    assert_eq!(eval("len(\"héllo\")"), Value::Int(5));
+}
+
+#[test]
+fn len_of_ranges_at_the_int_limits() {
+    assert_eq!(eval("len(0..=9223372036854775807)"), Value::Int(i64::MAX));
+    assert_eq!(eval("len(0..9223372036854775807)"), Value::Int(i64::MAX));
+    assert_eq!(eval("len(-9223372036854775807 - 1..9223372036854775807)"), Value::Int(i64::MAX));
+    assert_eq!(eval("len(9223372036854775807..=9223372036854775807)"), Value::Int(1));
+}
+
+#[test]
+fn for_in_walks_lists_maps_and_strings() {
+    assert_eq!(eval("var s = \"\"; for (x in [\"a\", \"b\"]) s = s + x; s"), Value::String("ab".to_string()));
+    assert_eq!(eval("var s = \"\"; for (k in {\"y\": 1, \"x\": 2}) s = s + k; s"), Value::String("xy".to_string()));
+    assert_eq!(eval("var s = \"\"; for (c in \"abc\") s = c + s; s"), Value::String("cba".to_string()));
+}
+
+#[test]
+fn break_stops_a_for_in_loop() {
+    assert_eq!(eval("var last = 0; for (i in 0..100) { if (i == 5) break; last = i; } last"), Value::Int(4));
+}
+
+#[test]
+fn ranges_display_and_need_integer_bounds() {
+    assert_eq!(eval("1..=3").to_string(), "1..=3");
+    assert!(Interpreter::new().eval_str("1.5..3").is_err());
+    assert!(Interpreter::new().eval_str("for (x in 42) print x;").is_err());
+}
@@ -0,0 +1,50 @@
+use rlox::{Interpreter, Value};
+
+fn closure_of(interpreter: &mut Interpreter, name: &str) -> usize {
+    match interpreter.eval_str(name).unwrap() {
+        Value::Function(function) => function.closure(),
+        other => panic!("{} is not a function: {}", name, other),
+    }
+}
+
+const COUNTER: &str = "
+fun makeCounter() {
+    var n = 0;
+    fun counter() { n = n + 1; return n; }
+    return counter;
+}
+var c = makeCounter();
+";
+
+#[test]
+fn self_referencing_closure_is_reachable_through_a_global() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str(COUNTER).unwrap();
+    let closure = closure_of(&mut interpreter, "c");
+
+    assert!(interpreter.reachable_envs().contains(&closure));
+}
+
+#[test]
+fn self_referencing_closure_is_unreachable_once_the_global_is_gone() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str(COUNTER).unwrap();
+    let closure = closure_of(&mut interpreter, "c");
+
+    // The closure env still holds "counter", whose closure is that same env
+    assert!(interpreter.remove_global("c").is_some());
+    assert!(!interpreter.reachable_envs().contains(&closure));
+}
+
+#[test]
+fn closures_inside_lists_keep_their_envs_alive() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str(COUNTER).unwrap();
+    let closure = closure_of(&mut interpreter, "c");
+    interpreter.eval_str("var fns = [0, c]; fns[0] = fns;").unwrap();
+    interpreter.remove_global("c");
+
+    assert!(interpreter.reachable_envs().contains(&closure));
+    interpreter.remove_global("fns");
+    assert!(!interpreter.reachable_envs().contains(&closure));
+}
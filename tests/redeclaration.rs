@@ -0,0 +1,53 @@
+use rlox::resolver::Resolver;
+use rlox::{ErrorReporter, Parser, Scanner};
+
+// (line, message) for each resolve error, and whether the reporter counted them as errors
+fn resolve_errors(source: &str) -> (Vec<(usize, String)>, bool) {
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let mut reporter = ErrorReporter::new();
+    reporter.set_color(false);
+    let statements = Parser::new(tokens).parse(&mut reporter).unwrap();
+
+    let mut resolver = Resolver::new();
+    resolver.resolve(&statements, &mut reporter);
+    let errors = resolver.errors().iter().map(|error| (error.token.line, error.message.clone())).collect();
+    (errors, reporter.had_error())
+}
+
+#[test]
+fn redeclaring_a_local_is_an_error() {
+    let (errors, had_error) = resolve_errors("{\n  var a = 1;\n  var a = 2;\n  print a;\n}");
+    assert_eq!(errors, vec![(3, "Already a variable with this name in this scope.".to_string())]);
+    assert!(had_error);
+}
+
+#[test]
+fn other_local_declarations_clash_too() {
+    assert_eq!(resolve_errors("fun f(a, a) { return a; }").0.len(), 1);
+    assert_eq!(resolve_errors("fun f(a) { var a = 1; return a; }").0.len(), 1, "parameters share the body's scope");
+    assert_eq!(resolve_errors("{ var g = 1; fun g() {} g(); }").0.len(), 1);
+}
+
+#[test]
+fn globals_can_be_redeclared() {
+    let (errors, had_error) = resolve_errors("var a = 1;\nvar a = 2;\nprint a;");
+    assert!(errors.is_empty());
+    assert!(!had_error);
+}
+
+#[test]
+fn shadowing_in_an_inner_scope_is_fine() {
+    let (errors, _) = resolve_errors("{ var a = 1; { var a = 2; print a; } print a; }");
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn a_redeclared_local_stops_the_script_from_running() {
+    let path = std::env::temp_dir().join("rlox_redeclared_local.lox");
+    std::fs::write(&path, "print \"before\";\n{\n  var a = 1;\n  var a = 2;\n}\n").unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rlox")).arg(&path).env("NO_COLOR", "1").output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("[line 4] Error: Already a variable with this name in this scope. at 'a'"), "{}", stderr);
+    assert_eq!(output.status.code(), Some(65));
+    assert!(output.stdout.is_empty());
+}
@@ -0,0 +1,38 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn run_repl(input: &str) -> (String, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rlox"))
+        .args(["--repl-history", "/dev/null"])
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    (String::from_utf8_lossy(&output.stdout).into_owned(), String::from_utf8_lossy(&output.stderr).into_owned())
+}
+
+#[test]
+fn repl_line_can_end_without_a_semicolon() {
+    let (stdout, stderr) = run_repl("var x = 1;\n1 + 2\nx = x + 41\nprint x;\n");
+    assert!(stderr.is_empty(), "{}", stderr);
+    assert!(stdout.contains("42"), "{}", stdout);
+}
+
+#[test]
+fn only_the_last_expression_on_a_line_can_skip_it() {
+    let (_, stderr) = run_repl("1 + 2 3 + 4\n");
+    assert!(stderr.contains("Expect ';' after value."), "{}", stderr);
+}
+
+#[test]
+fn files_still_need_the_semicolon() {
+    let path = std::env::temp_dir().join("rlox_no_semicolon.lox");
+    std::fs::write(&path, "1 + 2").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_rlox")).arg(&path).env("NO_COLOR", "1").output().unwrap();
+    assert_eq!(output.status.code(), Some(65));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Expect ';' after value."));
+}
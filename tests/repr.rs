@@ -0,0 +1,54 @@
+use rlox::{Interpreter, Value};
+
+fn repr(source: &str) -> String {
+    match Interpreter::new().eval_str(source).unwrap() {
+        Value::String(s) => s,
+        other => panic!("repr returned {}", other),
+    }
+}
+
+#[test]
+fn strings_are_quoted_and_escaped() {
+    assert_eq!(repr(r#"repr("a\nb")"#), r#""a\nb""#);
+    assert_eq!(repr(r#"repr("tab\there \"quoted\" back\\slash")"#), r#""tab\there \"quoted\" back\\slash""#);
+    assert_eq!(repr(r#"repr("1")"#), r#""1""#);
+}
+
+#[test]
+fn other_values_look_like_print() {
+    assert_eq!(repr("repr(1)"), "1");
+    assert_eq!(repr("repr(2.5)"), "2.5");
+    assert_eq!(repr("repr(nil)"), "nil");
+    assert_eq!(repr("repr([1, true])"), "[1, true]");
+}
+
+#[test]
+fn repr_of_a_string_reads_back_as_the_same_string() {
+    let mut interpreter = Interpreter::new();
+    let original = "line one\n\t\"two\" \\ three";
+    interpreter.define_native("original", 0, move |_, _| Ok(Value::String(original.to_string())));
+    let Value::String(source) = interpreter.eval_str("repr(original())").unwrap() else { panic!() };
+    assert_eq!(Interpreter::new().eval_str(&source).unwrap(), Value::String(original.to_string()));
+}
+
+#[test]
+fn functions_show_their_arity() {
+    assert_eq!(repr("fun add(a, b) { return a + b; } repr(add)"), "<fn add/2>");
+    assert_eq!(repr("fun tick() {} repr(tick)"), "<fn tick/0>");
+    // print keeps the short form
+    assert_eq!(repr("fun add(a, b) { return a + b; } str(add)"), "<fn add>");
+}
+
+#[test]
+fn nameless_function_signature() {
+    use rlox::function::{FunctionDeclaration, LoxFunction};
+    use rlox::{Token, TokenType};
+
+    let param = |name: &str| Token::new(TokenType::Identifier, name.to_string(), None, 1);
+    let declaration = FunctionDeclaration {
+        name: Token::new(TokenType::Identifier, String::new(), None, 1),
+        params: vec![param("a"), param("b")],
+        body: Vec::new(),
+    };
+    assert_eq!(LoxFunction::new(declaration, 0).signature(), "<fn anonymous/2>");
+}
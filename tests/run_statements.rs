@@ -0,0 +1,34 @@
+use rlox::stmt::Stmt;
+use rlox::{ErrorReporter, Interpreter, Parser, RuntimeError, Scanner, StatementFailed};
+
+fn parse(source: &str) -> Vec<Stmt> {
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    Parser::new(tokens).parse(&mut ErrorReporter::new()).unwrap()
+}
+
+#[test]
+fn counts_every_statement_run() {
+    let statements = parse("var a = 1; var b = a + 1; { b = b * 2; }");
+    assert_eq!(Interpreter::new().run_statements(&statements).unwrap(), 3);
+    assert_eq!(Interpreter::new().run_statements(&[]).unwrap(), 0);
+}
+
+#[test]
+fn error_says_which_statement_failed() {
+    let statements = parse("var a = 1;\na + nil;\nprint \"never\";");
+    let err = Interpreter::new().run_statements(&statements).unwrap_err();
+
+    assert_eq!(err.downcast_ref::<StatementFailed>().unwrap().index, 1);
+    assert_eq!(err.to_string(), "Statement 1 failed");
+    // The runtime error underneath is still there
+    let runtime_error = err.downcast_ref::<RuntimeError>().unwrap();
+    assert_eq!(runtime_error.token.line, 2);
+}
+
+#[test]
+fn repl_return_stops_the_count() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_repl_mode(true);
+    let statements = parse("var a = 1; return; var b = 2;");
+    assert_eq!(interpreter.run_statements(&statements).unwrap(), 2);
+}
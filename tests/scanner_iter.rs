@@ -0,0 +1,78 @@
+use rlox::{ScanError, Scanner, TokenType};
+
+#[test]
+fn iterator_yields_same_tokens_as_scan_tokens() {
+    let source = "var x = 1 + 2; // comment\nprint x;";
+    let collected = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let streamed: Vec<_> = Scanner::new(source.to_string()).map(Result::unwrap).collect();
+
+    assert_eq!(streamed.len(), collected.len());
+    for (a, b) in streamed.iter().zip(&collected) {
+        assert_eq!((&a.token_type, &a.lexeme, a.line, a.start), (&b.token_type, &b.lexeme, b.line, b.start));
+    }
+    assert_eq!(streamed.last().unwrap().token_type, TokenType::Eof);
+}
+
+#[test]
+fn errors_are_items_at_their_position() {
+    let results: Vec<_> = Scanner::new("1;\n  @ 2".to_string()).collect();
+    let errors: Vec<&ScanError> = results.iter().filter_map(|result| result.as_ref().err()).collect();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].line, 2);
+    assert_eq!(errors[0].start, 5);
+    assert_eq!(errors[0].message, "Unexpected character '@'");
+
+    // Scanning carries on after the error
+    let types: Vec<TokenType> = results.into_iter().filter_map(Result::ok).map(|token| token.token_type).collect();
+    assert_eq!(types, [TokenType::Number, TokenType::Semicolon, TokenType::Number, TokenType::Eof]);
+}
+
+#[test]
+fn iterator_is_lazy() {
+    // An error near the end doesn't stop the first tokens from arriving
+    let mut scanner = Scanner::new("var a = 1; \"unterminated".to_string());
+    assert_eq!(scanner.next().unwrap().unwrap().token_type, TokenType::Var);
+    assert_eq!(scanner.next().unwrap().unwrap().lexeme, "a");
+}
+
+#[test]
+fn scan_tokens_keeps_going_past_bad_characters() {
+    let mut scanner = Scanner::new("var a = 1 @;\nprint a $ 2;".to_string());
+    assert!(scanner.scan_tokens().is_err());
+
+    let errors: Vec<(usize, usize, &str)> =
+        scanner.errors().iter().map(|error| (error.line, error.start, error.message.as_str())).collect();
+    assert_eq!(errors, [(1, 10, "Unexpected character '@'"), (2, 21, "Unexpected character '$'")]);
+
+    // Everything around the bad characters is still there
+    let lexemes: Vec<&str> = scanner.tokens().iter().map(|token| token.lexeme.as_str()).collect();
+    assert_eq!(lexemes, ["var", "a", "=", "1", ";", "print", "a", "2", ";", ""]);
+}
+
+#[test]
+fn parse_errors_after_a_scan_error_are_reported_too() {
+    let path = std::env::temp_dir().join("rlox_scan_recovery.lox");
+    std::fs::write(&path, "print 1 @;\nvar = 2;\n").unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rlox")).arg(&path).env("NO_COLOR", "1").output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("[line 1] Error: Unexpected character '@'"), "{}", stderr);
+    assert!(stderr.contains("[line 2] Error"), "{}", stderr);
+    assert_eq!(output.status.code(), Some(65));
+    assert!(output.stdout.is_empty(), "nothing runs after a scan error");
+}
+
+#[test]
+fn non_ascii_bad_character_is_reported_not_a_crash() {
+    let path = std::env::temp_dir().join("rlox_scan_non_ascii.lox");
+    std::fs::write(&path, "var é = 1;\n").unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rlox")).arg(&path).env("NO_COLOR", "1").output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("[line 1] Error: Unexpected character 'é'"), "{}", stderr);
+    assert!(stderr.contains("1 | var é = 1;\n  |     ^\n"), "{}", stderr);
+    assert_eq!(output.status.code(), Some(65));
+
+    let mut scanner = Scanner::new("var é = 1;".to_string());
+    assert!(scanner.scan_tokens().is_err());
+    assert_eq!((scanner.errors()[0].start, scanner.errors()[0].length), (4, 2));
+}
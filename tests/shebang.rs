@@ -0,0 +1,30 @@
+use rlox::{Interpreter, Scanner, TokenType, Value};
+
+fn token_types(source: &str) -> Vec<TokenType> {
+    Scanner::new(source.to_string()).scan_tokens().unwrap().iter().map(|t| t.token_type.clone()).collect()
+}
+
+#[test]
+fn shebang_on_the_first_line_is_skipped() {
+    let types = token_types("#!/usr/bin/env lox\nprint 1;");
+    assert_eq!(types, vec![TokenType::Print, TokenType::Number, TokenType::Semicolon, TokenType::Eof]);
+
+    let mut interpreter = Interpreter::new();
+    assert_eq!(interpreter.eval_str("#!/usr/bin/env lox\nvar x = 2;\nx * 3").unwrap(), Value::Int(6));
+}
+
+#[test]
+fn lines_after_a_shebang_keep_their_numbers() {
+    let tokens = Scanner::new("#!lox\n\nprint 1;".to_string()).scan_tokens().unwrap().clone();
+    assert_eq!(tokens[0].line, 3);
+}
+
+#[test]
+fn hash_anywhere_else_is_an_error() {
+    let err = Scanner::new("print 1;\n#!/usr/bin/env lox".to_string()).scan_tokens().unwrap_err();
+    assert!(err.to_string().contains("[line 2] Error: Unexpected character '#'"), "{}", err);
+
+    // Not even at the start of the source when it isn't followed by '!'
+    assert!(Scanner::new("# comment\n".to_string()).scan_tokens().is_err());
+    assert!(Scanner::new(" #!lox\n".to_string()).scan_tokens().is_err());
+}
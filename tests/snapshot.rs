@@ -0,0 +1,27 @@
+use rlox::{Interpreter, Value};
+
+#[test]
+fn restore_brings_back_old_values() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var x = 1;").unwrap();
+
+    let snapshot = interpreter.snapshot();
+    interpreter.eval_str("x = 2; var y = 3;").unwrap();
+    assert_eq!(interpreter.eval_str("x").unwrap(), Value::Int(2));
+
+    interpreter.restore(snapshot);
+    assert_eq!(interpreter.eval_str("x").unwrap(), Value::Int(1));
+    assert!(interpreter.eval_str("y").is_err(), "y was defined after the snapshot");
+}
+
+#[test]
+fn snapshot_can_be_restored_after_a_function_call() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var count = 0; fun bump() { count = count + 1; }").unwrap();
+
+    let snapshot = interpreter.snapshot();
+    interpreter.eval_str("bump(); bump();").unwrap();
+    interpreter.restore(snapshot);
+
+    assert_eq!(interpreter.eval_str("bump(); count").unwrap(), Value::Int(1));
+}
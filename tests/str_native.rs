@@ -0,0 +1,26 @@
+use rlox::{Interpreter, Value};
+
+fn eval(source: &str) -> Value {
+    Interpreter::new().eval_str(source).unwrap()
+}
+
+#[test]
+fn str_concatenates_any_number_of_arguments() {
+    assert_eq!(eval(r#"str("x=", 3, " y=", true)"#), Value::String("x=3 y=true".to_string()));
+    assert_eq!(eval("str()"), Value::String(String::new()));
+    assert_eq!(eval("str(nil)"), Value::String("nil".to_string()));
+    assert_eq!(eval("str([1, 2], 0.5)"), Value::String("[1, 2]0.5".to_string()));
+}
+
+#[test]
+fn str_follows_print_precision() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_precision(Some(2));
+    assert_eq!(interpreter.eval_str("str(0.1 + 0.2)").unwrap(), Value::String("0.30".to_string()));
+}
+
+#[test]
+fn fixed_arity_natives_still_check_counts() {
+    let err = Interpreter::new().eval_str("hex(1, 2);").unwrap_err();
+    assert!(err.to_string().contains("Expected 1 arguments but got 2."));
+}
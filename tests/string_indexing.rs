@@ -0,0 +1,52 @@
+use rlox::{Interpreter, RuntimeError, RuntimeErrorKind, Value};
+
+fn eval(source: &str) -> Value {
+    Interpreter::new().eval_str(source).unwrap()
+}
+
+fn error(source: &str) -> RuntimeError {
+    Interpreter::new().eval_str(source).unwrap_err().downcast::<RuntimeError>().unwrap()
+}
+
+fn string(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+#[test]
+fn indexing_gives_one_character_strings() {
+    assert_eq!(eval("\"hello\"[1]"), string("e"));
+    assert_eq!(eval("\"hello\"[0]"), string("h"));
+    assert_eq!(eval("var s = \"abc\"; s[2]"), string("c"));
+}
+
+#[test]
+fn negative_indices_count_from_the_end() {
+    assert_eq!(eval("\"hello\"[-1]"), string("o"));
+    assert_eq!(eval("\"hello\"[-5]"), string("h"));
+}
+
+#[test]
+fn indices_count_characters_not_bytes() {
+    assert_eq!(eval("\"héllo\"[1]"), string("é"));
+    assert_eq!(eval("\"héllo\"[2]"), string("l"));
+}
+
+#[test]
+fn out_of_range_is_a_runtime_error() {
+    let err = error("\"hello\"[5]");
+    assert_eq!(err.message, "String index 5 is out of range for length 5.");
+    assert_eq!(err.kind(), RuntimeErrorKind::IndexOutOfRange);
+    assert_eq!(error("\"hello\"[-6]").message, "String index -6 is out of range for length 5.");
+    assert_eq!(error("\"\"[0]").message, "String index 0 is out of range for length 0.");
+}
+
+#[test]
+fn index_must_be_an_integer() {
+    assert_eq!(error("\"hello\"[1.5]").message, "String index must be an integer.");
+    assert_eq!(error("\"hello\"[\"a\"]").message, "String index must be an integer.");
+}
+
+#[test]
+fn strings_cant_be_assigned_through_an_index() {
+    assert_eq!(error("var s = \"abc\"; s[0] = \"x\";").kind(), RuntimeErrorKind::TypeError);
+}
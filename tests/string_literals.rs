@@ -0,0 +1,56 @@
+use rlox::{LiteralValue, Scanner};
+
+fn string_literal(source: &str) -> String {
+    let mut scanner = Scanner::new(source.to_string());
+    let tokens = scanner.scan_tokens().unwrap();
+    match &tokens[0].literal {
+        Some(LiteralValue::String(s)) => s.clone(),
+        other => panic!("expected a string literal, got {:?}", other),
+    }
+}
+
+#[test]
+fn escapes_in_normal_strings() {
+    assert_eq!(string_literal(r#""\n""#), "\n");
+    assert_eq!(string_literal(r#""a\tb""#), "a\tb");
+    assert_eq!(string_literal(r#""say \"hi\"""#), "say \"hi\"");
+    assert_eq!(string_literal(r#""back\\slash""#), "back\\slash");
+}
+
+#[test]
+fn raw_strings_keep_backslashes() {
+    assert_eq!(string_literal(r#"r"\n""#), "\\n");
+    assert_eq!(string_literal(r#"r"\n""#).len(), 2);
+    assert_eq!(string_literal(r#"r"C:\path\no\escapes""#), r"C:\path\no\escapes");
+}
+
+#[test]
+fn unknown_escape_is_an_error() {
+    let mut scanner = Scanner::new(r#""\q""#.to_string());
+    let err = scanner.scan_tokens().unwrap_err();
+    assert!(err.to_string().contains("Unknown escape sequence '\\q'"), "{}", err);
+}
+
+#[test]
+fn r_is_still_an_identifier() {
+    let mut scanner = Scanner::new("var r = 1;".to_string());
+    assert_eq!(scanner.scan_tokens().unwrap()[1].lexeme, "r");
+}
+
+#[test]
+fn backslash_newline_joins_lines() {
+    assert_eq!(string_literal("\"foo\\\nbar\""), "foobar");
+    assert_eq!(string_literal("\"foo\\\r\nbar\""), "foobar");
+    // Only the newline goes, indentation on the next line is kept
+    assert_eq!(string_literal("\"foo \\\n  bar\""), "foo   bar");
+}
+
+#[test]
+fn line_continuation_still_counts_the_line() {
+    let mut scanner = Scanner::new("\"foo\\\nbar\";\nprint".to_string());
+    let tokens = scanner.scan_tokens().unwrap();
+    assert_eq!(tokens[1].lexeme, ";");
+    assert_eq!(tokens[1].line, 2);
+    assert_eq!(tokens[2].lexeme, "print");
+    assert_eq!(tokens[2].line, 3);
+}
@@ -0,0 +1,28 @@
+use rlox::{Interpreter, RuntimeError, Value};
+
+fn eval(source: &str) -> Value {
+    Interpreter::new().eval_str(source).unwrap()
+}
+
+#[test]
+fn string_methods_match_the_free_functions() {
+    assert_eq!(eval("\"abc\".length()"), Value::Int(3));
+    assert_eq!(eval("\"abc\".upper()"), Value::String("ABC".to_string()));
+    assert_eq!(eval("\"  hi \".trim().upper()"), Value::String("HI".to_string()));
+    assert_eq!(eval("\"hello\".startsWith(\"he\")"), Value::Boolean(true));
+    assert_eq!(eval("var s = \"Abc\"; s.lower() == toLower(s)"), Value::Boolean(true));
+}
+
+#[test]
+fn methods_can_be_stored_and_called_later() {
+    assert_eq!(eval("var shout = \"hey\".upper; shout()"), Value::String("HEY".to_string()));
+}
+
+#[test]
+fn unknown_method_and_wrong_arity_are_errors() {
+    let err = Interpreter::new().eval_str("\"abc\".reverse();").unwrap_err();
+    assert_eq!(err.downcast::<RuntimeError>().unwrap().message, "Undefined method 'reverse' on string.");
+
+    let err = Interpreter::new().eval_str("\"abc\".upper(1);").unwrap_err();
+    assert_eq!(err.downcast::<RuntimeError>().unwrap().message, "Expected 0 arguments but got 1.");
+}
@@ -0,0 +1,37 @@
+use rlox::{Interpreter, RuntimeError, Scanner, Value};
+
+fn eval(source: &str) -> Value {
+    Interpreter::new().eval_str(source).unwrap()
+}
+
+fn string(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+#[test]
+fn case_conversion_is_unicode_aware() {
+    assert_eq!(eval("toUpper(\"hello\")"), string("HELLO"));
+    assert_eq!(eval("toLower(\"HeLLo\")"), string("hello"));
+    // ß has no single-letter uppercase form
+    assert_eq!(eval("toUpper(\"straße\")"), string("STRASSE"));
+    assert_eq!(eval("toLower(\"ÉCOLE\")"), string("école"));
+}
+
+#[test]
+fn trim_only_removes_the_ends() {
+    assert_eq!(eval("trim(\"   hello world\t\")"), string("hello world"));
+    assert_eq!(eval("trim(\"\")"), string(""));
+}
+
+#[test]
+fn string_natives_need_strings() {
+    let err = Interpreter::new().eval_str("toUpper(42);").unwrap_err();
+    assert_eq!(err.downcast::<RuntimeError>().unwrap().message, "Argument to toUpper() must be a string.");
+}
+
+#[test]
+fn non_ascii_source_scans_on_character_boundaries() {
+    let tokens = Scanner::new("print \"straße\"; var x = \"é\";".to_string()).scan_tokens().unwrap().clone();
+    let lexemes: Vec<&str> = tokens.iter().map(|token| token.lexeme.as_str()).collect();
+    assert_eq!(lexemes, ["print", "\"straße\"", ";", "var", "x", "=", "\"é\"", ";", ""]);
+}
@@ -0,0 +1,42 @@
+use rlox::{Interpreter, RuntimeError, Value};
+use std::process::Command;
+
+#[test]
+fn thrown_values_are_caught_as_strings() {
+    let mut interpreter = Interpreter::new();
+    let result = interpreter
+        .eval_str("var caught; try { throw \"bad input\"; } catch (e) { caught = e; } caught")
+        .unwrap();
+    assert_eq!(result, Value::String("bad input".to_string()));
+    assert_eq!(interpreter.eval_str("try { throw 40 + 2; } catch (e) { caught = e; } caught").unwrap(), Value::String("42".to_string()));
+}
+
+#[test]
+fn throw_unwinds_through_function_calls() {
+    let result = Interpreter::new()
+        .eval_str("fun check(n) { if (n < 0) throw \"negative\"; return n; } var r; try { check(-1); r = \"no\"; } catch (e) { r = e; } r")
+        .unwrap();
+    assert_eq!(result, Value::String("negative".to_string()));
+}
+
+#[test]
+fn thrown_errors_are_marked_as_thrown() {
+    let err = Interpreter::new().eval_str("throw \"oops\";").unwrap_err();
+    let err = err.downcast::<RuntimeError>().unwrap();
+    assert_eq!(err.message, "oops");
+    assert!(err.thrown);
+
+    let err = Interpreter::new().eval_str("1 / 0;").unwrap_err();
+    assert!(!err.downcast::<RuntimeError>().unwrap().thrown);
+}
+
+#[test]
+fn uncaught_throw_exits_with_70() {
+    let path = std::env::temp_dir().join("rlox_uncaught_throw.lox");
+    std::fs::write(&path, "print \"before\";\nthrow \"boom\";\nprint \"after\";\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rlox")).arg(&path).env("NO_COLOR", "1").output().unwrap();
+    assert_eq!(output.status.code(), Some(70));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "before\n");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("[line 2] Runtime Error: boom"));
+}
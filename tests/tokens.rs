@@ -0,0 +1,122 @@
+use rlox::{Scanner, Token, TokenType};
+
+fn tokens(source: &str) -> Vec<Token> {
+    Scanner::new(source.to_string()).scan_tokens().unwrap().clone()
+}
+
+#[test]
+fn token_display_is_readable() {
+    let tokens = tokens("var x = 42; \"hi\" 2.5 true");
+    assert_eq!(tokens[0].to_string(), "Var 'var'");
+    assert_eq!(tokens[1].to_string(), "Identifier 'x'");
+    assert_eq!(tokens[3].to_string(), "Number '42' (42)");
+    assert_eq!(tokens[5].to_string(), "String '\"hi\"' (\"hi\")");
+    assert_eq!(tokens[6].to_string(), "Number '2.5' (2.5)");
+    assert_eq!(tokens[7].to_string(), "True 'true' (true)");
+}
+
+#[test]
+fn offsets_cover_exactly_the_lexeme() {
+    let source = "var cafe = \"é\nb\";\n  print cafe ** 0x1F;";
+    for token in tokens(source) {
+        assert_eq!(&source[token.start..token.end], token.lexeme, "{}", token);
+    }
+}
+
+#[test]
+fn offsets_of_a_multiline_string_and_eof() {
+    let source = "x = \"one\ntwo\";";
+    let tokens = tokens(source);
+    assert_eq!((tokens[2].start, tokens[2].end), (4, 13));
+    assert_eq!(tokens[2].line, 2); // the line the string ends on
+
+    let eof = tokens.last().unwrap();
+    assert_eq!((eof.start, eof.end), (source.len(), source.len()));
+}
+
+fn columns(source: &str, tab_width: usize) -> Vec<usize> {
+    let mut scanner = Scanner::new(source.to_string());
+    scanner.set_tab_width(tab_width);
+    scanner.scan_tokens().unwrap().iter().map(|token| token.column).collect()
+}
+
+#[test]
+fn columns_count_characters_from_one() {
+    // var, x, =, "é", ;, then y on the next line
+    assert_eq!(columns("var x = \"é\";\n  y", 4), vec![1, 5, 7, 9, 12, 3, 4]);
+}
+
+#[test]
+fn tabs_advance_to_the_next_tab_stop() {
+    assert_eq!(columns("\tx", 4)[0], 5);
+    assert_eq!(columns("\tx", 8)[0], 9);
+    assert_eq!(columns("\t\tx", 4)[0], 9);
+    // A tab after some text only goes to the next stop, not a full width further
+    assert_eq!(columns("ab\tx", 4)[1], 5);
+    assert_eq!(columns("abcd\tx", 4)[1], 9);
+}
+
+#[test]
+fn default_tab_width_is_four() {
+    let tokens = tokens("\t\tvar");
+    assert_eq!(tokens[0].column, 9);
+}
+
+#[test]
+fn tab_width_flag_changes_json_columns() {
+    let path = std::env::temp_dir().join("rlox_tab_width.lox");
+    std::fs::write(&path, "\tvar x;").unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rlox"))
+        .args(["--emit-json", "--tab-width", "8"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains(r#"{"lexeme":"x","line":1,"column":13}"#));
+}
+
+#[test]
+fn same_kind_ignores_positions() {
+    use rlox::{LiteralValue, TokenType};
+
+    let token = |token_type, lexeme: &str, literal| Token::new(token_type, lexeme.to_string(), literal, 1);
+    let expected = [
+        token(TokenType::Var, "var", None),
+        token(TokenType::Identifier, "x", None),
+        token(TokenType::Equal, "=", None),
+        token(TokenType::Number, "42", Some(LiteralValue::Int(42))),
+        token(TokenType::Semicolon, ";", None),
+        token(TokenType::Eof, "", None),
+    ];
+    // On line 3, at an offset, and still the same kind
+    let scanned = tokens("\n\n  var x = 42;");
+    assert_eq!(scanned.len(), expected.len());
+    for (actual, expected) in scanned.iter().zip(&expected) {
+        assert!(actual.same_kind(expected), "{} vs {}", actual, expected);
+    }
+
+    let other = tokens("var x = 43;");
+    assert!(!other[3].same_kind(&expected[3]), "different literal");
+    assert!(!other[1].same_kind(&token(TokenType::Identifier, "y", None)), "different lexeme");
+    assert!(!other[0].same_kind(&token(TokenType::Identifier, "var", None)), "different type");
+}
+
+fn types(source: &str) -> Vec<TokenType> {
+    tokens(source).into_iter().map(|token| token.token_type).collect()
+}
+
+#[test]
+fn question_and_colon_for_the_ternary() {
+    use TokenType::*;
+    assert_eq!(types("a ? b : c"), [Identifier, Question, Identifier, Colon, Identifier, Eof]);
+    assert_eq!(types("a?b:c"), [Identifier, Question, Identifier, Colon, Identifier, Eof]);
+}
+
+#[test]
+fn question_dot_is_still_one_token() {
+    use TokenType::*;
+    assert_eq!(types("a?.b"), [Identifier, QuestionDot, Identifier, Eof]);
+    assert_eq!(types("a ? .5"), [Identifier, Question, Dot, Number, Eof]);
+    // There's no ?? yet, so it's two separate '?'
+    assert_eq!(types("a ?? b"), [Identifier, Question, Question, Identifier, Eof]);
+}
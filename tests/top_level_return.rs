@@ -0,0 +1,55 @@
+use rlox::{Interpreter, RuntimeError, Value};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[test]
+fn top_level_return_is_an_error_in_scripts() {
+    let err = Interpreter::new().eval_str("print 1;\nreturn 2;").unwrap_err();
+    let err = err.downcast::<RuntimeError>().unwrap();
+    assert_eq!(err.message, "Can't return from top-level code.");
+    assert_eq!(err.token.line, 2);
+}
+
+#[test]
+fn return_inside_functions_still_works() {
+    let mut interpreter = Interpreter::new();
+    let value = interpreter.eval_str("fun f() { { return 1; } } fun g() { return f() + 1; } g()").unwrap();
+    assert_eq!(value, Value::Int(2));
+    // and after a call, we are back at the top level
+    assert!(interpreter.eval_str("return;").is_err());
+}
+
+#[test]
+fn top_level_return_ends_the_line_in_repl_mode() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_repl_mode(true);
+    interpreter.eval_str("var a = 1; return; a = 2;").unwrap();
+    assert_eq!(interpreter.eval_str("a").unwrap(), Value::Int(1));
+    interpreter.eval_str("return a;").unwrap();
+    assert_eq!(interpreter.eval_str("a + 1").unwrap(), Value::Int(2));
+}
+
+#[test]
+fn repl_keeps_going_after_a_return() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rlox"))
+        .args(["--repl-history", "/dev/null"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"return;\nprint \"still here\";\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(String::from_utf8_lossy(&output.stdout).contains("still here"));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+}
+
+#[test]
+fn script_with_top_level_return_exits_with_70() {
+    let path = std::env::temp_dir().join("rlox_top_level_return.lox");
+    std::fs::write(&path, "return;\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_rlox")).arg(&path).env("NO_COLOR", "1").output().unwrap();
+    assert_eq!(output.status.code(), Some(70));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("[line 1] Runtime Error: Can't return from top-level code."));
+}
@@ -0,0 +1,36 @@
+use rlox::Interpreter;
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+// Collects the trace so the test can read it back
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn trace_logs_statements_and_values() {
+    let buffer = SharedBuffer::default();
+    let mut interpreter = Interpreter::new();
+    interpreter.enable_trace(buffer.clone());
+    interpreter.eval_str("var a = 1 + 2; { var b = a * 2; }").unwrap();
+
+    let trace = String::from_utf8(buffer.0.borrow().clone()).unwrap();
+    let lines: Vec<&str> = trace.lines().collect();
+    assert!(lines.contains(&"[stmt] var a = (+ 1 2)"), "{}", trace);
+    assert!(lines.contains(&"[expr] (+ 1 2) => 3"), "{}", trace);
+    assert!(lines.contains(&"[stmt] block"), "{}", trace);
+    // Inside the block everything is indented one level
+    assert!(lines.contains(&"  [stmt] var b = (* a 2)"), "{}", trace);
+    assert!(lines.contains(&"  [expr] (* a 2) => 6"), "{}", trace);
+}
@@ -0,0 +1,56 @@
+use rlox::ast_printer::AstPrinter;
+use rlox::stmt::Stmt;
+use rlox::{ErrorReporter, Interpreter, Parser, Scanner, Value};
+
+// Parsed the way the REPL and eval_str() parse, where the final expression may skip its ';'
+fn parse(source: &str) -> (Option<Vec<Stmt>>, Vec<String>) {
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let mut parser = Parser::new_with_trailing_expression(tokens);
+    let statements = parser.parse(&mut ErrorReporter::new());
+    let errors = parser.errors().iter().map(|err| err.message.clone()).collect();
+    (statements, errors)
+}
+
+#[test]
+fn last_expression_in_a_block_can_skip_its_semicolon() {
+    let (statements, errors) = parse("{ 1 + 2 }");
+    assert!(errors.is_empty(), "{:?}", errors);
+    let statements = statements.unwrap();
+    let Stmt::Block { statements: inner } = &statements[0] else { panic!("expected a block") };
+    assert_eq!(inner.len(), 1);
+    assert_eq!(AstPrinter::new().print_stmt(&inner[0]), "(+ 1 2)");
+}
+
+#[test]
+fn semicolon_less_expression_runs_normally() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("var x; if (true) { var y = 1; x = y + 2 }").unwrap();
+    assert_eq!(interpreter.eval_str("x").unwrap(), Value::Int(3));
+}
+
+#[test]
+fn only_the_final_expression_can_skip_it() {
+    // A block that isn't the end of the input still needs it
+    let (_, errors) = parse("var x; if (true) { x = 1 } print x;");
+    assert!(errors[0].starts_with("Expect ';' after value. at '}'"), "{:?}", errors);
+}
+
+#[test]
+fn scripts_need_the_semicolon_in_blocks_too() {
+    let path = std::env::temp_dir().join("rlox_block_no_semicolon.lox");
+    std::fs::write(&path, "var x; var c = true;\n{ if (c) x = 1 }\nprint x;\n").unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rlox")).arg(&path).env("NO_COLOR", "1").output().unwrap();
+    assert_eq!(output.status.code(), Some(65));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Expect ';' after value. at '}'"));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn missing_semicolon_mid_block_is_still_an_error() {
+    let (_, errors) = parse("{ 1 + 2 3; }");
+    assert!(errors[0].starts_with("Expect ';' after value. at '3'"), "{:?}", errors);
+
+    // Only expression statements get the exception
+    let (_, errors) = parse("{ print 1 }");
+    assert!(errors[0].starts_with("Expect ';' after expression. at '}'"), "{:?}", errors);
+}
@@ -0,0 +1,33 @@
+use rlox::{Interpreter, RuntimeError, Value};
+
+fn eval(source: &str) -> Value {
+    Interpreter::new().eval_str(source).unwrap()
+}
+
+#[test]
+fn catches_division_by_zero() {
+    let result = eval("var message; try { var x = 1 / 0; message = \"not reached\"; } catch (e) { message = e; } message");
+    assert_eq!(result, Value::String("Division by zero.".to_string()));
+}
+
+#[test]
+fn catch_is_skipped_without_an_error() {
+    assert_eq!(eval("var x = 1; try { x = 2; } catch (e) { x = 3; } x"), Value::Int(2));
+}
+
+#[test]
+fn error_variable_only_lives_in_the_catch_block() {
+    let err = Interpreter::new().eval_str("try { nil + 1; } catch (e) { } e;").unwrap_err();
+    assert_eq!(err.downcast::<RuntimeError>().unwrap().message, "Undefined variable 'e'.");
+}
+
+#[test]
+fn errors_in_the_catch_block_propagate() {
+    assert!(Interpreter::new().eval_str("try { 1 / 0; } catch (e) { -\"x\"; }").is_err());
+}
+
+#[test]
+fn return_and_break_pass_through_try() {
+    assert_eq!(eval("fun f() { try { return 1; } catch (e) { return 2; } return 3; } f()"), Value::Int(1));
+    assert_eq!(eval("var i = 0; while (true) { try { i = i + 1; if (i == 3) break; } catch (e) { } } i"), Value::Int(3));
+}
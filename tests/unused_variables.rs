@@ -0,0 +1,68 @@
+use rlox::resolver::Resolver;
+use rlox::{ErrorReporter, Parser, Scanner};
+
+fn warnings_with(source: &str, warn_unused_parameters: bool) -> Vec<(usize, String)> {
+    let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap().clone();
+    let mut reporter = ErrorReporter::new();
+    reporter.set_color(false);
+    let statements = Parser::new(tokens).parse(&mut reporter).unwrap();
+
+    let mut resolver = Resolver::new();
+    resolver.set_warn_unused_parameters(warn_unused_parameters);
+    resolver.resolve(&statements, &mut reporter);
+    assert!(!reporter.had_error(), "warnings must not count as errors");
+    resolver.warnings().iter().map(|warning| (warning.token.line, warning.message.clone())).collect()
+}
+
+fn warnings(source: &str) -> Vec<(usize, String)> {
+    warnings_with(source, false)
+}
+
+#[test]
+fn unused_block_local_is_reported() {
+    let source = "{\n  var used = 1;\n  var unused = 2;\n  print used;\n}";
+    assert_eq!(warnings(source), vec![(3, "Local variable 'unused' is never used.".to_string())]);
+}
+
+#[test]
+fn used_locals_are_not_reported() {
+    assert!(warnings("{ var a = 1; var b; b = a; }").is_empty());
+    assert!(warnings("fun f() { var x = 1; fun g() { return x; } return g; }").is_empty());
+    assert!(warnings("{ for (i in 0..3) print i; }").is_empty());
+}
+
+#[test]
+fn globals_and_underscore_names_are_exempt() {
+    assert!(warnings("var global = 1; fun f() {}").is_empty());
+    assert!(warnings("{ var _ignored = 1; }").is_empty());
+}
+
+#[test]
+fn parameters_are_only_reported_when_asked() {
+    let source = "fun f(a, b) { return a; }";
+    assert!(warnings(source).is_empty());
+    assert_eq!(warnings_with(source, true), vec![(1, "Parameter 'b' is never used.".to_string())]);
+}
+
+#[test]
+fn shadowing_tracks_the_inner_variable() {
+    let source = "{ var x = 1;\n{ var x = 2; print x; } }";
+    assert_eq!(warnings(source), vec![(1, "Local variable 'x' is never used.".to_string())]);
+}
+
+#[test]
+fn mutually_recursive_local_functions_are_used() {
+    let source = "{\n  fun isEven(n) { if (n == 0) return true; return isOdd(n - 1); }\n  fun isOdd(n) { if (n == 0) return false; return isEven(n - 1); }\n  print isEven(4);\n}";
+    assert!(warnings(source).is_empty(), "{:?}", warnings(source));
+
+    // Inside a function body too, and an unused local next to them is still reported
+    let source = "fun f() {\n  fun a() { return b(); }\n  fun b() { return a(); }\n  var c = 1;\n  return a;\n}";
+    assert_eq!(warnings(source), vec![(4, "Local variable 'c' is never used.".to_string())]);
+}
+
+#[test]
+fn warning_format() {
+    let mut reporter = ErrorReporter::new();
+    reporter.set_color(false);
+    assert_eq!(reporter.format_warning(3, "Local variable 'x' is never used."), "[line 3] Warning: Local variable 'x' is never used.");
+}
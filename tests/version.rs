@@ -0,0 +1,20 @@
+use std::process::Command;
+
+fn run(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_rlox")).args(args).output().unwrap()
+}
+
+#[test]
+fn version_prints_the_package_version() {
+    let output = run(&["--version"]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let expected = format!("rlox {} (", env!("CARGO_PKG_VERSION"));
+    assert!(stdout.starts_with(&expected), "{}", stdout);
+    assert!(stdout.trim_end().ends_with(')'), "{}", stdout);
+}
+
+#[test]
+fn version_only_works_on_its_own() {
+    assert_eq!(run(&["--version", "script.lox"]).status.code(), Some(64));
+}